@@ -122,9 +122,11 @@ impl CommonDeriveInput {
 ///
 /// Performs coherence checks (e.g., to be `zero_copy` the struct must be `repr(C)`).
 fn check_attrs(input: &DeriveInput) -> (bool, bool, bool) {
-    let is_repr_c = input.attrs.iter().any(|x| {
-        x.meta.path().is_ident("repr") && x.meta.require_list().unwrap().tokens.to_string() == "C"
-    });
+    // repr(transparent) types have the same layout of their only
+    // non-zero-sized field, so they are treated as repr(C) types.
+    let is_repr_c = reprs(&input.attrs)
+        .iter()
+        .any(|x| x == "C" || x == "transparent");
     let is_zero_copy = input
         .attrs
         .iter()
@@ -135,7 +137,21 @@ fn check_attrs(input: &DeriveInput) -> (bool, bool, bool) {
         .any(|x| x.meta.path().is_ident("deep_copy"));
     if is_zero_copy && !is_repr_c {
         panic!(
-            "Type {} is declared as zero copy, but it is not repr(C)",
+            "Type {} is declared as zero copy, but it is not repr(C) or repr(transparent)",
+            input.ident
+        );
+    }
+    if !matches!(input.data, Data::Enum(_))
+        && input.attrs.iter().any(|x| x.path().is_ident("epserde"))
+    {
+        panic!(
+            "Type {} uses the epserde attribute, which is supported only on enums and fields",
+            input.ident
+        );
+    }
+    if matches!(input.data, Data::Union(_)) && !is_zero_copy {
+        panic!(
+            "Type {} is a union, but unions must be declared as zero copy",
             input.ident
         );
     }
@@ -149,22 +165,557 @@ fn check_attrs(input: &DeriveInput) -> (bool, bool, bool) {
     (is_repr_c, is_zero_copy, is_deep_copy)
 }
 
+/// Return the list of representation hints of a type (e.g., `C` and `u8`
+/// for `#[repr(C, u8)]`).
+fn reprs(attrs: &[syn::Attribute]) -> Vec<String> {
+    attrs
+        .iter()
+        .filter(|x| x.meta.path().is_ident("repr"))
+        .flat_map(|x| {
+            x.parse_args_with(Punctuated::<syn::Meta, token::Comma>::parse_terminated)
+                .unwrap()
+        })
+        .map(|x| x.to_token_stream().to_string())
+        .collect()
+}
+
+/// The integer types that can be used as enum tags.
+const TAG_TYPES: [(&str, i128, i128); 12] = [
+    ("u8", u8::MIN as i128, u8::MAX as i128),
+    ("u16", u16::MIN as i128, u16::MAX as i128),
+    ("u32", u32::MIN as i128, u32::MAX as i128),
+    ("u64", u64::MIN as i128, u64::MAX as i128),
+    ("usize", usize::MIN as i128, usize::MAX as i128),
+    ("i8", i8::MIN as i128, i8::MAX as i128),
+    ("i16", i16::MIN as i128, i16::MAX as i128),
+    ("i32", i32::MIN as i128, i32::MAX as i128),
+    ("i64", i64::MIN as i128, i64::MAX as i128),
+    ("isize", isize::MIN as i128, isize::MAX as i128),
+    ("u128", u128::MIN as i128, i128::MAX),
+    ("i128", i128::MIN, i128::MAX),
+];
+
+/// Options specified on an enum using the `epserde` attribute.
+#[derive(Default)]
+struct EnumAttrs {
+    /// The type of the tag (`tag = "…"`).
+    tag: Option<String>,
+    /// Whether slices of the enum use the indexed layout (`indexed`).
+    indexed: bool,
+}
+
+impl EnumAttrs {
+    /// Parse the `epserde` attributes of an enum.
+    fn new(attrs: &[syn::Attribute]) -> Self {
+        let mut enum_attrs = Self::default();
+        attrs
+            .iter()
+            .filter(|x| x.path().is_ident("epserde"))
+            .for_each(|x| {
+                x.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("tag") {
+                        let tag: syn::LitStr = meta.value()?.parse()?;
+                        enum_attrs.tag = Some(tag.value());
+                        Ok(())
+                    } else if meta.path.is_ident("indexed") {
+                        enum_attrs.indexed = true;
+                        Ok(())
+                    } else {
+                        Err(meta.error("unsupported epserde enum attribute"))
+                    }
+                })
+                .unwrap()
+            });
+        enum_attrs
+    }
+}
+
+/// Return the type of the tag of an enum, and the value of the tag
+/// of each variant.
+///
+/// The type of the tag is given by the attribute `#[epserde(tag = "…")]`, if
+/// present, or by the integer representation of the enum (e.g.,
+/// `#[repr(u8)]`), if present, and it is `usize` otherwise. Explicit
+/// discriminants must be integer literals; variants without an explicit
+/// discriminant get the value of the previous variant plus one, as in Rust.
+fn enum_tags(
+    name: &syn::Ident,
+    attrs: &[syn::Attribute],
+    e: &syn::DataEnum,
+) -> (syn::Ident, Vec<i128>) {
+    let tag_type = EnumAttrs::new(attrs).tag;
+
+    let reprs = reprs(attrs);
+    let tag_type = tag_type
+        .or_else(|| {
+            reprs
+                .into_iter()
+                .find(|x| TAG_TYPES.iter().any(|(ty, _, _)| ty == x))
+        })
+        .unwrap_or_else(|| "usize".to_string());
+
+    let &(_, min, max) = TAG_TYPES
+        .iter()
+        .find(|(ty, _, _)| *ty == tag_type)
+        .unwrap_or_else(|| {
+            panic!(
+                "Type {} has tag type {}, but tag types must be primitive integer types",
+                name, tag_type
+            )
+        });
+
+    let mut next = 0;
+    let tags = e
+        .variants
+        .iter()
+        .map(|variant| {
+            let tag = match &variant.discriminant {
+                None => next,
+                Some((_, expr)) => discriminant_value(expr).unwrap_or_else(|| {
+                    panic!(
+                        "Variant {} of type {} has a discriminant that is not an integer literal",
+                        variant.ident, name
+                    )
+                }),
+            };
+            if tag < min || tag > max {
+                panic!(
+                    "Variant {} of type {} has discriminant {}, which does not fit the tag type {}",
+                    variant.ident, name, tag, tag_type
+                );
+            }
+            next = tag + 1;
+            tag
+        })
+        .collect::<Vec<_>>();
+
+    for (i, tag) in tags.iter().enumerate() {
+        if tags[..i].contains(tag) {
+            panic!("Type {} has duplicate discriminant value {}", name, tag);
+        }
+    }
+
+    (
+        syn::Ident::new(&tag_type, proc_macro2::Span::call_site()),
+        tags,
+    )
+}
+
+/// Evaluate a discriminant, if it is a (possibly negated) integer literal.
+fn discriminant_value(expr: &syn::Expr) -> Option<i128> {
+    match expr {
+        syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Int(lit),
+            ..
+        }) => lit.base10_parse().ok(),
+        syn::Expr::Unary(syn::ExprUnary {
+            op: syn::UnOp::Neg(_),
+            expr,
+            ..
+        }) => discriminant_value(expr).map(|x| -x),
+        syn::Expr::Group(syn::ExprGroup { expr, .. })
+        | syn::Expr::Paren(syn::ExprParen { expr, .. }) => discriminant_value(expr),
+        _ => None,
+    }
+}
+
+/// Return a token stream for an integer literal, possibly negative.
+fn tag_literal(tag: i128) -> proc_macro2::TokenStream {
+    let lit = proc_macro2::Literal::u128_unsuffixed(tag.unsigned_abs());
+    if tag < 0 {
+        quote! { -#lit }
+    } else {
+        quote! { #lit }
+    }
+}
+
+/// Options specified on a field using the `epserde` attribute.
+#[derive(Default)]
+struct FieldAttrs {
+    /// The version in which the field was added (`since = N`).
+    since: Option<u16>,
+    /// Whether the field is filled with `Default::default()` when missing (`default`).
+    default: bool,
+}
+
+impl FieldAttrs {
+    /// Parse the `epserde` attributes of a field.
+    fn new(field: &syn::Field) -> Self {
+        let mut attrs = Self::default();
+        field
+            .attrs
+            .iter()
+            .filter(|x| x.path().is_ident("epserde"))
+            .for_each(|x| {
+                x.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("since") {
+                        let since: syn::LitInt = meta.value()?.parse()?;
+                        attrs.since = Some(since.base10_parse()?);
+                        Ok(())
+                    } else if meta.path.is_ident("default") {
+                        attrs.default = true;
+                        Ok(())
+                    } else {
+                        Err(meta.error("unsupported epserde field attribute"))
+                    }
+                })
+                .unwrap()
+            });
+        attrs
+    }
+}
+
+/// Return the number of stable fields of a struct, that is, fields
+/// without the attribute `#[epserde(since = N, default)]`, and the version
+/// of the struct, that is, the largest version of a field, or zero.
+///
+/// Performs coherence checks (e.g., fields with `since` must be trailing,
+/// must specify `default`, and must appear in nondecreasing order of version).
+fn check_fields_attrs(name: &syn::Ident, fields: &syn::Fields, is_zero_copy: bool) -> (usize, u16) {
+    let attrs = fields.iter().map(FieldAttrs::new).collect::<Vec<_>>();
+    let num_stable = attrs.iter().take_while(|x| x.since.is_none()).count();
+    let mut last_since = 0;
+    for (field_idx, attr) in attrs.iter().enumerate() {
+        match attr.since {
+            None if field_idx >= num_stable => panic!(
+                "Field {} of type {} has no version, but it follows a field with a version",
+                field_idx, name
+            ),
+            None if attr.default => panic!(
+                "Field {} of type {} specifies a default, but it has no version",
+                field_idx, name
+            ),
+            None => {}
+            Some(_) if !attr.default => panic!(
+                "Field {} of type {} has a version, but it does not specify a default",
+                field_idx, name
+            ),
+            Some(since) => {
+                if since == 0 || since < last_since {
+                    panic!(
+                        "Field {} of type {} has version {}, but versions must be positive and nondecreasing",
+                        field_idx, name, since
+                    );
+                }
+                last_since = since;
+            }
+        }
+    }
+    if is_zero_copy && num_stable < attrs.len() {
+        panic!(
+            "Type {} is declared as zero copy, but it has fields with a version",
+            name
+        );
+    }
+    (num_stable, last_since)
+}
+
+/// Generate the implementation of `IndexedEnum` for an enum
+/// with the attribute `#[epserde(indexed)]`.
+#[allow(clippy::too_many_arguments)]
+fn indexed_enum_impl(
+    name: &syn::Ident,
+    generics: &proc_macro2::TokenStream,
+    generics_names: &proc_macro2::TokenStream,
+    where_clause: &WhereClause,
+    e: &syn::DataEnum,
+    tag_type: &syn::Ident,
+    tags: &[proc_macro2::TokenStream],
+    invalid_tag: &proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let mut where_clause = where_clause.clone();
+    let mut variants_names = vec![];
+    let mut variant_ids = vec![];
+    let mut payload_variants = vec![];
+    let mut payload_types = vec![];
+    let mut payload_idxs = vec![];
+    let mut check_arms = vec![];
+    let mut get_arms = vec![];
+
+    for (variant_id, (variant, tag)) in e.variants.iter().zip(tags).enumerate() {
+        let ident = &variant.ident;
+        variants_names.push(ident);
+        variant_ids.push(variant_id);
+        match &variant.fields {
+            syn::Fields::Unit => {
+                check_arms.push(quote! { #tag => Ok(true), });
+                get_arms.push(quote! { #tag => Self::#ident, });
+            }
+            syn::Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+                let ty = &fields.unnamed[0].ty;
+                let idx = syn::Index::from(payload_types.len());
+                where_clause
+                    .predicates
+                    .push(syn::parse_quote!(#ty: epserde::traits::ZeroCopy + epserde::ser::SerializeInner + epserde::deser::DeserializeInner));
+                check_arms.push(quote! { #tag => Ok(pos < payloads.#idx.len()), });
+                get_arms.push(quote! { #tag => Self::#ident(payloads.#idx[pos]), });
+                payload_variants.push(ident);
+                payload_types.push(ty);
+                payload_idxs.push(idx);
+            }
+            _ => panic!(
+                "Variant {} of indexed enum {} must be a unit variant or a tuple variant with a single field",
+                ident, name
+            ),
+        }
+    }
+
+    let num_variants = variants_names.len();
+
+    quote! {
+        #[automatically_derived]
+        impl<#generics> epserde::traits::IndexedEnum for #name<#generics_names> #where_clause {
+            type Tag = #tag_type;
+            type Payloads<'epserde_payloads> = ( #( &'epserde_payloads [#payload_types], )* );
+            type FullPayloads = ( #( epserde::__private::Vec<#payload_types>, )* );
+            const NUM_VARIANTS: usize = #num_variants;
+
+            #[inline(always)]
+            fn _variant(&self) -> usize {
+                match self {
+                    #( Self::#variants_names { .. } => #variant_ids, )*
+                }
+            }
+
+            #[inline(always)]
+            fn _tag(&self) -> Self::Tag {
+                match self {
+                    #( Self::#variants_names { .. } => #tags, )*
+                }
+            }
+
+            #[allow(unreachable_patterns, unused_variables)]
+            fn _serialize_payloads(
+                items: &[Self],
+                backend: &mut impl epserde::ser::WriteWithNames,
+            ) -> epserde::ser::Result<()> {
+                #(
+                    epserde::ser::helpers::serialize_slice_zero_iter(
+                        backend,
+                        items.iter().filter(|item| matches!(item, Self::#payload_variants(_))).count(),
+                        items.iter().filter_map(|item| match item {
+                            Self::#payload_variants(payload) => Some(*payload),
+                            _ => None,
+                        }),
+                    )?;
+                )*
+                Ok(())
+            }
+
+            #[allow(unused_variables)]
+            fn _deserialize_full_payloads(
+                backend: &mut impl epserde::deser::ReadWithPos,
+            ) -> epserde::deser::Result<Self::FullPayloads> {
+                Ok(( #( epserde::deser::helpers::deserialize_full_vec_zero::<#payload_types>(backend)?, )* ))
+            }
+
+            #[allow(unused_variables)]
+            fn _deserialize_eps_payloads<'epserde_payloads>(
+                backend: &mut epserde::deser::SliceWithPos<'epserde_payloads>,
+            ) -> epserde::deser::Result<Self::Payloads<'epserde_payloads>> {
+                Ok(( #( epserde::deser::helpers::deserialize_eps_slice_zero::<#payload_types>(backend)?, )* ))
+            }
+
+            #[allow(unused_variables)]
+            fn _as_payloads(payloads: &Self::FullPayloads) -> Self::Payloads<'_> {
+                ( #( payloads.#payload_idxs.as_slice(), )* )
+            }
+
+            #[allow(unused_variables)]
+            fn _check(
+                payloads: &Self::Payloads<'_>,
+                tag: Self::Tag,
+                pos: usize,
+            ) -> epserde::deser::Result<bool> {
+                match tag {
+                    #( #check_arms )*
+                    tag => Err(epserde::deser::Error::InvalidTag(#invalid_tag)),
+                }
+            }
+
+            #[allow(unused_variables)]
+            fn _get(payloads: &Self::Payloads<'_>, tag: Self::Tag, pos: usize) -> Self {
+                match tag {
+                    #( #get_arms )*
+                    tag => panic!("Invalid tag: {}", tag),
+                }
+            }
+        }
+    }
+}
+
+/// Generate a compile-time assertion that a boolean constant of `SerializeInner`
+/// (or its negation, if `negate` is true) is true, returning a pair of token streams, the first to be used at the
+/// item level, and the second inside a method.
+///
+/// The assertion is at the item level for non-generic types, so that errors are
+/// reported even if the type is never serialized, and inside a method otherwise,
+/// as items cannot refer to generic parameters.
+fn const_assert(
+    name: &syn::Ident,
+    is_generic: bool,
+    constant: proc_macro2::TokenStream,
+    negate: bool,
+    message: String,
+) -> (proc_macro2::TokenStream, proc_macro2::TokenStream) {
+    let not = if negate { quote!(!) } else { quote!() };
+    if is_generic {
+        (
+            quote! {},
+            quote! {
+                const { assert!(#not <Self as epserde::ser::SerializeInner>::#constant, #message) };
+            },
+        )
+    } else {
+        (
+            quote! {
+                const _: () = assert!(#not <#name as epserde::ser::SerializeInner>::#constant, #message);
+            },
+            quote! {},
+        )
+    }
+}
+
+/// Replace the type parameters in `params` with `with` in a type, returning
+/// `None` if a parameter is used as a path prefix (e.g., `T::Item`), as
+/// in that case the replacement would not be a valid type.
+fn replace_params(
+    tokens: proc_macro2::TokenStream,
+    params: &[String],
+    with: &proc_macro2::TokenStream,
+) -> Option<proc_macro2::TokenStream> {
+    use proc_macro2::{Group, TokenTree};
+    let mut out = proc_macro2::TokenStream::new();
+    let mut iter = tokens.into_iter().peekable();
+    let mut after_quote = false;
+    while let Some(tt) = iter.next() {
+        let is_quote = matches!(&tt, TokenTree::Punct(p) if p.as_char() == '\'');
+        match tt {
+            TokenTree::Group(g) => {
+                let mut group =
+                    Group::new(g.delimiter(), replace_params(g.stream(), params, with)?);
+                group.set_span(g.span());
+                out.extend([TokenTree::Group(group)]);
+            }
+            TokenTree::Ident(ident) if !after_quote && params.contains(&ident.to_string()) => {
+                if matches!(iter.peek(), Some(TokenTree::Punct(p)) if p.as_char() == ':') {
+                    return None;
+                }
+                out.extend(quote!((#with)));
+            }
+            tt => out.extend([tt]),
+        }
+        after_quote = is_quote;
+    }
+    Some(out)
+}
+
+/// Generate a compile-time check that the deserialization type of a
+/// deep-copy type is covariant in its lifetime, as required by the safety
+/// contract of `DeserializeInner`.
+///
+/// The deserialization type replaces the type parameters in `replaced` with
+/// their deserialization types, which are covariant, but opaque to the
+/// compiler. Thus, we check instead that the fields mentioning such parameters
+/// are covariant when the parameters are replaced by references.
+fn covariance_check(
+    generics: &syn::Generics,
+    replaced: &[String],
+    fields_types: &[proc_macro2::TokenStream],
+) -> proc_macro2::TokenStream {
+    let long = quote!(&'epserde_long ());
+    let short = quote!(&'epserde_short ());
+    let mut long_types = vec![];
+    let mut short_types = vec![];
+    for ty in fields_types {
+        let tokens = ty.clone();
+        let (Some(long_ty), Some(short_ty)) = (
+            replace_params(tokens.clone(), replaced, &long),
+            replace_params(tokens.clone(), replaced, &short),
+        ) else {
+            continue;
+        };
+        // Fields not mentioning replaced parameters do not depend on the lifetime
+        if long_ty.to_string() != tokens.to_string() {
+            long_types.push(long_ty);
+            short_types.push(short_ty);
+        }
+    }
+
+    if long_types.is_empty() {
+        return quote!();
+    }
+
+    // The remaining parameters, without bounds, which are not necessary
+    let params = generics.params.iter().filter_map(|param| match param {
+        GenericParam::Type(t) if replaced.contains(&t.ident.to_string()) => None,
+        GenericParam::Type(t) => Some(t.ident.to_token_stream()),
+        GenericParam::Lifetime(l) => Some(l.lifetime.to_token_stream()),
+        GenericParam::Const(c) => {
+            let (ident, ty) = (&c.ident, &c.ty);
+            Some(quote!(const #ident: #ty))
+        }
+    });
+
+    quote! {
+        const _: () = {
+            // If this function does not compile, the deserialization type is not covariant
+            #[allow(dead_code, clippy::type_complexity)]
+            fn check_covariance<'epserde_long: 'epserde_short, 'epserde_short, #(#params,)*>(
+                x: (#(#long_types,)*),
+            ) -> (#(#short_types,)*) {
+                x
+            }
+        };
+    }
+}
+
 /// Generate an ε-serde implementation for custom types.
 ///
 /// It generates implementations for the traits `CopyType`,
 /// `MaxSizeOf`, `TypeHash`, `ReprHash`, `SerializeInner`,
 /// and `DeserializeInner`.
 ///
-/// Presently we do not support unions.
-///
 /// The attribute `zero_copy` can be used to generate an implementation for a zero-copy
-/// type, but the type must be `repr(C)` and all fields must be zero-copy.
+/// type, but the type must be `repr(C)` or `repr(transparent)` and all fields must be
+/// zero-copy. Unions are supported, but they must be zero-copy.
 ///
 /// If you do not specify `zero_copy`, the macro assumes your structure is deep-copy.
 /// However, if you have a structure that could be zero-copy, but has no attribute,
-/// a warning will be issued every time you serialize. The warning can be silenced adding
-/// the explicity attribute `deep_copy`.
-#[proc_macro_derive(Epserde, attributes(zero_copy, deep_copy))]
+/// a constant assertion fails at compile time (for generic structures, when
+/// serialization is instantiated for type parameters that are zero-copy). The
+/// error can be silenced adding the explicit attribute `deep_copy`.
+///
+/// Deep-copy structures can grow in a backward-compatible way by appending
+/// fields marked with the attribute `#[epserde(since = N, default)]`, where
+/// `N` is the (positive) version in which the field was introduced. Versioned
+/// fields must follow all other fields, in nondecreasing version order. The
+/// version of a structure with versioned fields, that is, the largest version
+/// of its fields, is written in the serialized data, and fields missing from
+/// data serialized by an older version of the structure are filled with
+/// their [`Default`] value, both in full-copy and in ε-copy deserialization.
+/// Neither versioned fields nor the version contribute to the type hash, as
+/// otherwise data serialized by older versions of the structure would be
+/// rejected; the type hash records only whether there are versioned fields,
+/// so data serialized before the first versioned field was added are
+/// rejected rather than misread.
+///
+/// Variants of deep-copy enums are identified by a tag, whose type is given
+/// by the attribute `#[epserde(tag = "…")]`, or by the integer representation
+/// of the enum (e.g., `#[repr(u8)]`), or it is `usize` if neither is present.
+/// The value of the tag is the discriminant of the variant, so explicit
+/// discriminants (which must be integer literals) make the serialized data
+/// independent of the order of the variants. Discriminants and the type of
+/// the tag are part of the type hash, which is computed in increasing order
+/// of discriminant, and thus it is independent of the order of the variants, too.
+///
+/// Deep-copy enums whose variants are unit variants or tuple variants with a
+/// single zero-copy field can be marked with `#[epserde(indexed)]`: vectors and
+/// boxed slices of such enums are serialized as a slice of tags, a slice of
+/// positions, and a slice of payloads for each variant, and they are ε-copy
+/// deserialized as an `IndexedSlice`, which provides constant-time access by
+/// index. See `IndexedEnum` for more information.
+#[proc_macro_derive(Epserde, attributes(zero_copy, deep_copy, epserde))]
 pub fn epserde_derive(input: TokenStream) -> TokenStream {
     // Cloning input for type hash
     let input_for_typehash = input.clone();
@@ -193,6 +744,29 @@ pub fn epserde_derive(input: TokenStream) -> TokenStream {
         ..
     } = CommonDeriveInput::new(derive_input.clone(), vec![]);
 
+    // Compile-time checks on the copy type of the type
+    let is_generic = !derive_input.generics.params.is_empty();
+    let (zero_copy_check, zero_copy_check_inline) = const_assert(
+        &name,
+        is_generic,
+        quote!(IS_ZERO_COPY),
+        false,
+        format!(
+            "Type {} is declared as zero copy, but it is not zero copy (all fields must be zero copy, and the type must be repr(C) or repr(transparent))",
+            name
+        ),
+    );
+    let (mismatch_check, mismatch_check_inline) = const_assert(
+        &name,
+        is_generic,
+        quote!(ZERO_COPY_MISMATCH),
+        true,
+        format!(
+            "Type {} is zero copy, but it has not been declared as such; use the #[zero_copy] attribute to make it zero copy, or the #[deep_copy] attribute to make it deep copy",
+            name
+        ),
+    );
+
     let out = match derive_input.data {
         Data::Struct(s) => {
             let mut fields_types = vec![];
@@ -229,12 +803,70 @@ pub fn epserde_derive(input: TokenStream) -> TokenStream {
             s.fields.iter().for_each(|field| {
                 let ty = &field.ty;
                 if type_names_raw.contains(&ty.to_token_stream().to_string()) {
-                    methods.push(syn::parse_quote!(_deserialize_eps_inner));
+                    methods.push(syn::parse_quote!(deserialize_eps_field));
                 } else {
-                    methods.push(syn::parse_quote!(_deserialize_full_inner));
+                    methods.push(syn::parse_quote!(deserialize_full_field));
                 }
             });
 
+            // Fields with a version (i.e., `#[epserde(since = N, default)]`)
+            // are trailing; if there are any, we write the version of the
+            // structure (the largest version of a field) before the fields,
+            // and at deserialization time we fill with defaults the fields
+            // that are missing.
+            let (num_stable, version) = check_fields_attrs(&name, &s.fields, is_zero_copy);
+            let is_versioned = version != 0;
+            let fields_since = s
+                .fields
+                .iter()
+                .map(|field| FieldAttrs::new(field).since.unwrap_or(0))
+                .collect::<Vec<_>>();
+
+            let (write_version, read_version) = if is_versioned {
+                (
+                    quote! {
+                        backend.write("version", &#version)?;
+                    },
+                    quote! {
+                        let version = epserde::deser::helpers::deserialize_version(backend, #version)?;
+                    },
+                )
+            } else {
+                (quote! {}, quote! {})
+            };
+
+            let mut fields_full_des = vec![];
+            let mut fields_eps_des = vec![];
+            fields_types
+                .iter()
+                .zip(fields_names.iter())
+                .zip(methods.iter())
+                .enumerate()
+                .for_each(|(field_idx, ((ty, field_name), method))| {
+                    let full_des = quote!(epserde::deser::helpers::deserialize_full_field::<#ty>(backend, stringify!(#field_name))?);
+                    let eps_des = quote!(epserde::deser::helpers::#method::<#ty>(backend, stringify!(#field_name))?);
+                    if field_idx < num_stable {
+                        fields_full_des.push(full_des);
+                        fields_eps_des.push(eps_des);
+                    } else {
+                        let since = fields_since[field_idx];
+                        fields_full_des.push(quote! {
+                            if version >= #since {
+                                #full_des
+                            } else {
+                                Default::default()
+                            }
+                        });
+                        fields_eps_des.push(quote! {
+                            if version >= #since {
+                                #eps_des
+                            } else {
+                                Default::default()
+                            }
+                        });
+                    }
+                });
+
             // Gather deserialization types of fields,
             // which are necessary to derive the deserialization type.
             let deser_type_generics = generics_name_vec
@@ -330,6 +962,18 @@ pub fn epserde_derive(input: TokenStream) -> TokenStream {
                 }
             });
 
+            let covariance_check = covariance_check(
+                &derive_input.generics,
+                &generic_types
+                    .iter()
+                    .map(|ty| ty.to_token_stream().to_string())
+                    .collect::<Vec<_>>(),
+                &fields_types
+                    .iter()
+                    .map(|ty| ty.to_token_stream())
+                    .collect::<Vec<_>>(),
+            );
+
             if is_zero_copy {
                 quote! {
                     #[automatically_derived]
@@ -337,6 +981,8 @@ pub fn epserde_derive(input: TokenStream) -> TokenStream {
                         type Copy = epserde::traits::Zero;
                     }
 
+                    #zero_copy_check
+
                     #[automatically_derived]
                     impl<#generics_serialize> epserde::ser::SerializeInner for #name<#generics_names> #where_clause_ser {
                         // Compute whether the type could be zero copy
@@ -349,6 +995,7 @@ pub fn epserde_derive(input: TokenStream) -> TokenStream {
 
                         #[inline(always)]
                         fn _serialize_inner(&self, backend: &mut impl epserde::ser::WriteWithNames) -> epserde::ser::Result<()> {
+                            #zero_copy_check_inline
                             // No-op code that however checks that all fields are zero-copy.
                             fn test<T: epserde::traits::ZeroCopy>() {}
                             #(
@@ -359,7 +1006,7 @@ pub fn epserde_derive(input: TokenStream) -> TokenStream {
                     }
 
                     #[automatically_derived]
-                    impl<#generics_deserialize> epserde::deser::DeserializeInner for #name<#generics_names> #where_clause_des
+                    unsafe impl<#generics_deserialize> epserde::deser::DeserializeInner for #name<#generics_names> #where_clause_des
                     {
                         fn _deserialize_full_inner(
                             backend: &mut impl epserde::deser::ReadWithPos,
@@ -385,20 +1032,24 @@ pub fn epserde_derive(input: TokenStream) -> TokenStream {
                         type Copy = epserde::traits::Deep;
                     }
 
+                    #mismatch_check
+
                     #[automatically_derived]
                     impl<#generics_serialize> epserde::ser::SerializeInner for #name<#generics_names> #where_clause_ser {
-                        // Compute whether the type could be zero copy
-                        const IS_ZERO_COPY: bool = #is_repr_c #(
+                        // Compute whether the type could be zero copy (types
+                        // with versioned fields cannot)
+                        const IS_ZERO_COPY: bool = ! #is_versioned && #is_repr_c #(
                             && <#fields_types>::IS_ZERO_COPY
                         )*;
 
                         // Compute whether the type could be zero copy but it is not declared as such,
                         // and the attribute `deep_copy` is missing.
-                        const ZERO_COPY_MISMATCH: bool = ! #is_deep_copy #(&& <#fields_types>::IS_ZERO_COPY)*;
+                        const ZERO_COPY_MISMATCH: bool = ! #is_versioned && ! #is_deep_copy #(&& <#fields_types>::IS_ZERO_COPY)*;
 
                         #[inline(always)]
                         fn _serialize_inner(&self, backend: &mut impl epserde::ser::WriteWithNames) -> epserde::ser::Result<()> {
-                            epserde::ser::helpers::check_mismatch::<Self>();
+                            #mismatch_check_inline
+                            #write_version
                             #(
                                 backend.write(stringify!(#fields_names), &self.#fields_names)?;
                             )*
@@ -406,15 +1057,18 @@ pub fn epserde_derive(input: TokenStream) -> TokenStream {
                         }
                     }
 
+                    #covariance_check
+
                     #[automatically_derived]
-                    impl<#generics_deserialize> epserde::deser::DeserializeInner for #name<#generics_names> #where_clause_des {
+                    unsafe impl<#generics_deserialize> epserde::deser::DeserializeInner for #name<#generics_names> #where_clause_des {
                         fn _deserialize_full_inner(
                             backend: &mut impl epserde::deser::ReadWithPos,
                         ) -> core::result::Result<Self, epserde::deser::Error> {
                             use epserde::deser::DeserializeInner;
+                            #read_version
                             Ok(#name{
                                 #(
-                                    #fields_names: <#fields_types>::_deserialize_full_inner(backend)?,
+                                    #fields_names: #fields_full_des,
                                 )*
                             })
                         }
@@ -426,9 +1080,10 @@ pub fn epserde_derive(input: TokenStream) -> TokenStream {
                         ) -> core::result::Result<Self::DeserType<'deserialize_eps_inner_lifetime>, epserde::deser::Error>
                         {
                             use epserde::deser::DeserializeInner;
+                            #read_version
                             Ok(#name{
                                 #(
-                                    #fields_names: <#fields_types>::#methods(backend)?,
+                                    #fields_names: #fields_eps_des,
                                 )*
                             })
                         }
@@ -458,13 +1113,21 @@ pub fn epserde_derive(input: TokenStream) -> TokenStream {
             let mut non_generic_fields = Vec::new();
             let mut non_generic_types = Vec::new();
             let mut fields_types = Vec::new();
+            let (tag_type, tags) = enum_tags(&name, &derive_input.attrs, &e);
+            let tags = tags.into_iter().map(tag_literal).collect::<Vec<_>>();
+            let is_indexed = EnumAttrs::new(&derive_input.attrs).indexed;
+            if is_indexed && is_zero_copy {
+                panic!("Type {} is declared as both zero copy and indexed", name);
+            }
             e.variants.iter().enumerate().for_each(|(variant_id, variant)| {
                 variants_names.push(variant.ident.to_token_stream());
+                let tag = &tags[variant_id];
                 match &variant.fields {
                 syn::Fields::Unit => {
                     variants.push(variant.ident.to_token_stream());
                     variant_ser.push(quote! {{
-                        backend.write("tag", &#variant_id)?;
+                        let tag: #tag_type = #tag;
+                        backend.write("tag", &tag)?;
                     }});
                     variant_full_des.push(quote! {});
                     variant_eps_des.push(quote! {});
@@ -513,10 +1176,10 @@ pub fn epserde_derive(input: TokenStream) -> TokenStream {
                                     bounds: bounds_des,
                             }));
 
-                            if type_names_raw.contains(&ty.to_token_stream().to_string()) {
-                                methods.push(syn::parse_quote!(_deserialize_eps_inner));
+                            if !is_indexed && type_names_raw.contains(&ty.to_token_stream().to_string()) {
+                                methods.push(syn::parse_quote!(deserialize_eps_field));
                             } else {
-                                methods.push(syn::parse_quote!(_deserialize_full_inner));
+                                methods.push(syn::parse_quote!(deserialize_full_field));
                             }
                         });
                     let ident = variant.ident.clone();
@@ -525,19 +1188,20 @@ pub fn epserde_derive(input: TokenStream) -> TokenStream {
                     });
                     fields_types.extend(var_fields_types.clone());
                     variant_ser.push(quote! {
-                        backend.write("tag", &#variant_id)?;
+                        let tag: #tag_type = #tag;
+                        backend.write("tag", &tag)?;
                         #(
                             backend.write(stringify!(#var_fields_names), #var_fields_names)?;
                         )*
                     });
                     variant_full_des.push(quote! {
                         #(
-                            #var_fields_names: <#var_fields_types>::_deserialize_full_inner(backend)?,
+                            #var_fields_names: epserde::deser::helpers::deserialize_full_field::<#var_fields_types>(backend, stringify!(#var_fields_names))?,
                         )*
                     });
                     variant_eps_des.push(quote! {
                         #(
-                            #var_fields_names: <#var_fields_types>::#methods(backend)?,
+                            #var_fields_names: epserde::deser::helpers::#methods::<#var_fields_types>(backend, stringify!(#var_fields_names))?,
                         )*
                     });
                 }
@@ -594,10 +1258,10 @@ pub fn epserde_derive(input: TokenStream) -> TokenStream {
                                     bounds: bounds_des,
                             }));
 
-                            if type_names_raw.contains(&ty.to_token_stream().to_string()) {
-                                methods.push(syn::parse_quote!(_deserialize_eps_inner));
+                            if !is_indexed && type_names_raw.contains(&ty.to_token_stream().to_string()) {
+                                methods.push(syn::parse_quote!(deserialize_eps_field));
                             } else {
-                                methods.push(syn::parse_quote!(_deserialize_full_inner));
+                                methods.push(syn::parse_quote!(deserialize_full_field));
                             }
 
                         });
@@ -609,19 +1273,20 @@ pub fn epserde_derive(input: TokenStream) -> TokenStream {
                     fields_types.extend(var_fields_types.clone());
 
                     variant_ser.push(quote! {
-                        backend.write("tag", &#variant_id)?;
+                        let tag: #tag_type = #tag;
+                        backend.write("tag", &tag)?;
                         #(
                             backend.write(stringify!(#var_fields_names), #var_fields_names)?;
                         )*
                     });
                     variant_full_des.push(quote! {
                         #(
-                            #var_fields_vars    : <#var_fields_types>::_deserialize_full_inner(backend)?,
+                            #var_fields_vars: epserde::deser::helpers::deserialize_full_field::<#var_fields_types>(backend, stringify!(#var_fields_names))?,
                         )*
                     });
                     variant_eps_des.push(quote! {
                         #(
-                            #var_fields_vars    : <#var_fields_types>::#methods(backend)?,
+                            #var_fields_vars: epserde::deser::helpers::#methods::<#var_fields_types>(backend, stringify!(#var_fields_names))?,
                         )*
                     });
                 }
@@ -643,7 +1308,58 @@ pub fn epserde_derive(input: TokenStream) -> TokenStream {
                 })
                 .collect::<Vec<_>>();
 
-            let tag = (0..variants.len()).collect::<Vec<_>>();
+            // The deserialization type of indexed enums is the enum itself,
+            // as their payloads are zero-copy
+            let deser_type = if is_indexed {
+                quote!(#name<#generics_names>)
+            } else {
+                quote!(#name<#(#deser_type_generics,)*>)
+            };
+
+            // Invalid tags are reported as i128, which can represent all
+            // tags except u128 tags larger than i128::MAX (which are never
+            // valid, as discriminants are i128)
+            let invalid_tag = if tag_type == "u128" {
+                quote! { i128::try_from(tag).unwrap_or(i128::MAX) }
+            } else {
+                quote! { tag as i128 }
+            };
+
+            let copy_type = if is_indexed {
+                quote!(epserde::traits::Indexed)
+            } else {
+                quote!(epserde::traits::Deep)
+            };
+
+            let indexed_impl = if is_indexed {
+                indexed_enum_impl(
+                    &name,
+                    &generics,
+                    &generics_names,
+                    &where_clause,
+                    &e,
+                    &tag_type,
+                    &tags,
+                    &invalid_tag,
+                )
+            } else {
+                quote! {}
+            };
+
+            // The deserialization type of indexed enums does not depend on
+            // the lifetime
+            let covariance_check = if is_indexed {
+                quote!()
+            } else {
+                covariance_check(
+                    &derive_input.generics,
+                    &generic_types
+                        .iter()
+                        .map(|ty| ty.to_string())
+                        .collect::<Vec<_>>(),
+                    &fields_types,
+                )
+            };
 
             if is_zero_copy {
                 quote! {
@@ -652,6 +1368,8 @@ pub fn epserde_derive(input: TokenStream) -> TokenStream {
                         type Copy = epserde::traits::Zero;
                     }
 
+                    #zero_copy_check
+
                     #[automatically_derived]
                     impl<#generics_serialize> epserde::ser::SerializeInner for #name<#generics_names> #where_clause_ser {
                         // Compute whether the type could be zero copy
@@ -664,6 +1382,7 @@ pub fn epserde_derive(input: TokenStream) -> TokenStream {
 
                         #[inline(always)]
                         fn _serialize_inner(&self, backend: &mut impl epserde::ser::WriteWithNames) -> epserde::ser::Result<()> {
+                            #zero_copy_check_inline
                             // No-op code that however checks that all fields are zero-copy.
                             fn test<T: epserde::traits::ZeroCopy>() {}
                             #(
@@ -674,7 +1393,7 @@ pub fn epserde_derive(input: TokenStream) -> TokenStream {
                     }
 
                     #[automatically_derived]
-                    impl<#generics_deserialize> epserde::deser::DeserializeInner for #name<#generics_names> #where_clause_des {
+                    unsafe impl<#generics_deserialize> epserde::deser::DeserializeInner for #name<#generics_names> #where_clause_des {
                         fn _deserialize_full_inner(
                             backend: &mut impl epserde::deser::ReadWithPos,
                         ) -> core::result::Result<Self, epserde::deser::Error> {
@@ -695,9 +1414,13 @@ pub fn epserde_derive(input: TokenStream) -> TokenStream {
                 quote! {
                     #[automatically_derived]
                     impl<#generics> epserde::traits::CopyType for  #name<#generics_names> #where_clause {
-                        type Copy = epserde::traits::Deep;
+                        type Copy = #copy_type;
                     }
 
+                    #indexed_impl
+
+                    #mismatch_check
+
                     #[automatically_derived]
                     impl<#generics_serialize> epserde::ser::SerializeInner for #name<#generics_names> #where_clause_ser {
                         // Compute whether the type could be zero copy
@@ -707,11 +1430,11 @@ pub fn epserde_derive(input: TokenStream) -> TokenStream {
 
                         // Compute whether the type could be zero copy but it is not declared as such,
                         // and the attribute `deep_copy` is missing.
-                        const ZERO_COPY_MISMATCH: bool = ! #is_deep_copy #(&& <#fields_types>::IS_ZERO_COPY)*;
+                        const ZERO_COPY_MISMATCH: bool = ! #is_indexed && ! #is_deep_copy #(&& <#fields_types>::IS_ZERO_COPY)*;
 
                         #[inline(always)]
                         fn _serialize_inner(&self, backend: &mut impl epserde::ser::WriteWithNames) -> epserde::ser::Result<()> {
-                            epserde::ser::helpers::check_mismatch::<Self>();
+                            #mismatch_check_inline
                             match self {
                                 #(
                                    Self::#variants => { #variant_ser }
@@ -721,39 +1444,107 @@ pub fn epserde_derive(input: TokenStream) -> TokenStream {
                         }
                     }
 
+                    #covariance_check
+
                     #[automatically_derived]
-                    impl<#generics_deserialize> epserde::deser::DeserializeInner for #name<#generics_names> #where_clause_des {
+                    unsafe impl<#generics_deserialize> epserde::deser::DeserializeInner for #name<#generics_names> #where_clause_des {
                         fn _deserialize_full_inner(
                             backend: &mut impl epserde::deser::ReadWithPos,
                         ) -> core::result::Result<Self, epserde::deser::Error> {
                             use epserde::deser::DeserializeInner;
-                            match usize::_deserialize_full_inner(backend)? {
+                            match epserde::deser::helpers::deserialize_full_field::<#tag_type>(backend, "tag")? {
                                 #(
-                                    #tag => Ok(Self::#variants_names{ #variant_full_des }),
+                                    #tags => Ok(Self::#variants_names{ #variant_full_des }),
                                 )*
-                                tag => Err(epserde::deser::Error::InvalidTag(tag)),
+                                tag => Err(epserde::deser::Error::InvalidTag(#invalid_tag)),
                             }
                         }
 
-                        type DeserType<'epserde_desertype> = #name<#(#deser_type_generics,)*>;
+                        type DeserType<'epserde_desertype> = #deser_type;
 
                         fn _deserialize_eps_inner<'deserialize_eps_inner_lifetime>(
                             backend: &mut epserde::deser::SliceWithPos<'deserialize_eps_inner_lifetime>,
                         ) -> core::result::Result<Self::DeserType<'deserialize_eps_inner_lifetime>, epserde::deser::Error>
                         {
                             use epserde::deser::DeserializeInner;
-                            match usize::_deserialize_full_inner(backend)? {
+                            match epserde::deser::helpers::deserialize_full_field::<#tag_type>(backend, "tag")? {
                                 #(
-                                    #tag => Ok(Self::DeserType::<'_>::#variants_names{ #variant_eps_des }),
+                                    #tags => Ok(Self::DeserType::<'_>::#variants_names{ #variant_eps_des }),
                                 )*
-                                tag => Err(epserde::deser::Error::InvalidTag(tag)),
+                                tag => Err(epserde::deser::Error::InvalidTag(#invalid_tag)),
                             }
                         }
                     }
                 }
             }
         }
-        _ => todo!("Union types are not currently supported"),
+        Data::Union(u) => {
+            let fields_types = u
+                .fields
+                .named
+                .iter()
+                .map(|field| &field.ty)
+                .collect::<Vec<_>>();
+            let where_clause = derive_input
+                .generics
+                .where_clause
+                .clone()
+                .unwrap_or_else(|| WhereClause {
+                    where_token: token::Where::default(),
+                    predicates: Punctuated::new(),
+                });
+
+            // Unions are always zero copy (see check_attrs).
+            quote! {
+                #[automatically_derived]
+                impl<#generics> epserde::traits::CopyType for  #name<#generics_names> #where_clause {
+                    type Copy = epserde::traits::Zero;
+                }
+
+                #zero_copy_check
+
+                #[automatically_derived]
+                impl<#generics_serialize> epserde::ser::SerializeInner for #name<#generics_names> #where_clause {
+                    // Compute whether the type could be zero copy
+                    const IS_ZERO_COPY: bool = #is_repr_c #(
+                        && <#fields_types as epserde::ser::SerializeInner>::IS_ZERO_COPY
+                    )*;
+
+                    // The type is declared as zero copy, so a fortiori there is no mismatch.
+                    const ZERO_COPY_MISMATCH: bool = false;
+
+                    #[inline(always)]
+                    fn _serialize_inner(&self, backend: &mut impl epserde::ser::WriteWithNames) -> epserde::ser::Result<()> {
+                        #zero_copy_check_inline
+                        // No-op code that however checks that all fields are zero-copy.
+                        fn test<T: epserde::traits::ZeroCopy>() {}
+                        #(
+                            test::<#fields_types>();
+                        )*
+                        epserde::ser::helpers::serialize_zero(backend, self)
+                    }
+                }
+
+                #[automatically_derived]
+                unsafe impl<#generics_deserialize> epserde::deser::DeserializeInner for #name<#generics_names> #where_clause
+                {
+                    fn _deserialize_full_inner(
+                        backend: &mut impl epserde::deser::ReadWithPos,
+                    ) -> core::result::Result<Self, epserde::deser::Error> {
+                        epserde::deser::helpers::deserialize_full_zero::<Self>(backend)
+                    }
+
+                    type DeserType<'epserde_desertype> = &'epserde_desertype #name<#generics_names>;
+
+                    fn _deserialize_eps_inner<'deserialize_eps_inner_lifetime>(
+                        backend: &mut epserde::deser::SliceWithPos<'deserialize_eps_inner_lifetime>,
+                    ) -> core::result::Result<Self::DeserType<'deserialize_eps_inner_lifetime>, epserde::deser::Error>
+                    {
+                        epserde::deser::helpers::deserialize_eps_zero::<Self>(backend)
+                    }
+                }
+            }
+        }
     };
 
     let mut out: TokenStream = out.into();
@@ -767,7 +1558,7 @@ pub fn epserde_derive(input: TokenStream) -> TokenStream {
 /// It generates implementations just for the traits
 /// `MaxSizeOf`, `TypeHash`, and `ReprHash`. See the documentation
 /// of [`epserde_derive`] for more information.
-#[proc_macro_derive(TypeInfo, attributes(zero_copy, deep_copy))]
+#[proc_macro_derive(TypeInfo, attributes(zero_copy, deep_copy, epserde))]
 pub fn epserde_type_hash(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
     let (_, is_zero_copy, _) = check_attrs(&input);
@@ -803,9 +1594,22 @@ pub fn epserde_type_hash(input: TokenStream) -> TokenStream {
 
     let out = match input.data {
         Data::Struct(s) => {
+            // Fields with a version are not part of the type hash, so that
+            // adding them does not break compatibility. For the same reason,
+            // the version of the structure is not part of the type hash, but
+            // of the serialized data; we just record that the structure has
+            // versioned fields, as only in that case the version is written.
+            let (num_stable, version) = check_fields_attrs(&name, &s.fields, is_zero_copy);
+            let versioned_hash = if version != 0 {
+                quote! { "Versioned".hash(hasher); }
+            } else {
+                quote! {}
+            };
+
             let fields_names = s
                 .fields
                 .iter()
+                .take(num_stable)
                 .enumerate()
                 .map(|(field_idx, field)| {
                     field
@@ -819,6 +1623,7 @@ pub fn epserde_type_hash(input: TokenStream) -> TokenStream {
             let fields_types = s
                 .fields
                 .iter()
+                .take(num_stable)
                 .map(|field| field.ty.to_owned())
                 .collect::<Vec<_>>();
 
@@ -892,7 +1697,7 @@ pub fn epserde_type_hash(input: TokenStream) -> TokenStream {
                     impl<#generics_maxsizeof> epserde::traits::MaxSizeOf for #name<#generics_names> #where_clause{
                         #[inline(always)]
                         fn max_size_of() -> usize {
-                            let mut max_size_of = std::mem::align_of::<Self>();
+                            let mut max_size_of = ::core::mem::align_of::<Self>();
                             // Recurse on all fields.
                             #(
                                 if max_size_of < <#fields_types as epserde::traits::MaxSizeOf>::max_size_of() {
@@ -916,6 +1721,8 @@ pub fn epserde_type_hash(input: TokenStream) -> TokenStream {
                             // No alignment, so we do not hash in anything.
                             // Hash in DeepCopy
                             "DeepCopy".hash(hasher);
+                            // Hash in whether there are versioned fields
+                            #versioned_hash
                             // Hash the values of generic constants
                             #(
                                 #const_names_vec.hash(hasher);
@@ -964,13 +1771,34 @@ pub fn epserde_type_hash(input: TokenStream) -> TokenStream {
                     predicates: Punctuated::new(),
                 });
 
-            let mut var_type_hashes = Vec::new();
             let mut var_repr_hashes = Vec::new();
             let mut var_max_size_ofs = Vec::new();
 
-            e.variants.iter().for_each(|variant| {
+            let (tag_type, tags) = enum_tags(&name, &input.attrs, &e);
+            let copy_type = if EnumAttrs::new(&input.attrs).indexed {
+                "Indexed"
+            } else {
+                "DeepCopy"
+            };
+            // The type of the tag is relevant only for deep-copy enums
+            let tag_type_hash = if is_zero_copy {
+                quote! {}
+            } else {
+                let tag_type = tag_type.to_string();
+                quote! { #tag_type.hash(hasher); }
+            };
+
+            // The type hash of the variants is computed in increasing tag
+            // order, so that it is independent of the order of the variants
+            let mut var_type_hashes_by_tag = Vec::new();
+
+            e.variants.iter().zip(tags).for_each(|(variant, tag_value)| {
                 let ident = variant.ident.to_owned();
-                let mut var_type_hash = quote! { stringify!(#ident).hash(hasher); };
+                let tag = tag_value.to_string();
+                let mut var_type_hash = quote! {
+                    stringify!(#ident).hash(hasher);
+                    #tag.hash(hasher);
+                };
                 let mut var_repr_hash = quote! { };
                 let mut var_max_size_of = quote! {  };
                 match &variant.fields {
@@ -1024,11 +1852,17 @@ pub fn epserde_type_hash(input: TokenStream) -> TokenStream {
                             });
                     }
                 }
-                var_type_hashes.push(var_type_hash);
+                var_type_hashes_by_tag.push((tag_value, var_type_hash));
                 var_repr_hashes.push(var_repr_hash);
                 var_max_size_ofs.push(var_max_size_of);
             });
 
+            var_type_hashes_by_tag.sort_by_key(|(tag, _)| *tag);
+            let var_type_hashes = var_type_hashes_by_tag
+                .into_iter()
+                .map(|(_, var_type_hash)| var_type_hash)
+                .collect::<Vec<_>>();
+
             // Build type name
             let name_literal = name.to_string();
 
@@ -1060,7 +1894,7 @@ pub fn epserde_type_hash(input: TokenStream) -> TokenStream {
                             #(
                                 #const_names_raw.hash(hasher);
                             )*
-                            // Hash in struct and field names.
+                            // Hash in enum name, discriminants, and field names.
                             #name_literal.hash(hasher);
                             #(
                                 #var_type_hashes
@@ -1094,7 +1928,7 @@ pub fn epserde_type_hash(input: TokenStream) -> TokenStream {
                     impl<#generics_maxsizeof> epserde::traits::MaxSizeOf for #name<#generics_names> #where_clause{
                         #[inline(always)]
                         fn max_size_of() -> usize {
-                            let mut max_size_of = std::mem::align_of::<Self>();
+                            let mut max_size_of = ::core::mem::align_of::<Self>();
                             #(
                                 #var_max_size_ofs
                             )*
@@ -1113,8 +1947,8 @@ pub fn epserde_type_hash(input: TokenStream) -> TokenStream {
                         ) {
                             use core::hash::Hash;
                             // No alignment, so we do not hash in anything.
-                            // Hash in DeepCopy
-                            "DeepCopy".hash(hasher);
+                            // Hash in DeepCopy or Indexed
+                            #copy_type.hash(hasher);
                             // Hash the values of generic constants
                             #(
                                 #const_names_vec.hash(hasher);
@@ -1123,7 +1957,9 @@ pub fn epserde_type_hash(input: TokenStream) -> TokenStream {
                             #(
                                 #const_names_raw.hash(hasher);
                             )*
-                            // Hash in struct and field names.
+                            // Hash in the type of the tag.
+                            #tag_type_hash
+                            // Hash in enum name, discriminants, and field names.
                             #name_literal.hash(hasher);
                             #(
                                 #var_type_hashes
@@ -1149,7 +1985,119 @@ pub fn epserde_type_hash(input: TokenStream) -> TokenStream {
                 }
             }
         }
-        _ => todo!("Union types are not currently supported"),
+        Data::Union(u) => {
+            let where_clause = input
+                .generics
+                .where_clause
+                .clone()
+                .unwrap_or_else(|| WhereClause {
+                    where_token: token::Where::default(),
+                    predicates: Punctuated::new(),
+                });
+
+            let fields_names = u
+                .fields
+                .named
+                .iter()
+                .map(|field| field.ident.as_ref().unwrap().to_string())
+                .collect::<Vec<_>>();
+
+            let fields_types = u
+                .fields
+                .named
+                .iter()
+                .map(|field| field.ty.to_owned())
+                .collect::<Vec<_>>();
+
+            // Build type name
+            let name_literal = name.to_string();
+
+            // Add reprs
+            let repr = input
+                .attrs
+                .iter()
+                .filter(|x| x.meta.path().is_ident("repr"))
+                .map(|x| x.meta.require_list().unwrap().tokens.to_string())
+                .collect::<Vec<_>>();
+
+            quote! {
+                #[automatically_derived]
+                impl<#generics_typehash> epserde::traits::TypeHash for #name<#generics_names> #where_clause{
+
+                    #[inline(always)]
+                    fn type_hash(
+                        hasher: &mut impl core::hash::Hasher,
+                    ) {
+                        use core::hash::Hash;
+                        // Hash in ZeroCopy
+                        "ZeroCopy".hash(hasher);
+                        // Hash in Union, so that a union and a struct
+                        // with the same fields have different hashes
+                        "Union".hash(hasher);
+                        // Hash the values of generic constants
+                        #(
+                            #const_names_vec.hash(hasher);
+                        )*
+                        // Hash the identifiers of generic constants
+                        #(
+                            #const_names_raw.hash(hasher);
+                        )*
+                        // Hash in union and field names.
+                        #name_literal.hash(hasher);
+                        #(
+                            #fields_names.hash(hasher);
+                        )*
+                        // Recurse on all fields.
+                        #(
+                            <#fields_types as epserde::traits::TypeHash>::type_hash(hasher);
+                        )*
+                    }
+                }
+
+                impl<#generics_reprhash> epserde::traits::ReprHash for #name<#generics_names> #where_clause{
+                    #[inline(always)]
+                    fn repr_hash(
+                        hasher: &mut impl core::hash::Hasher,
+                        offset_of: &mut usize,
+                    ) {
+                        use core::hash::Hash;
+                        // Hash in size, as padding is given by MaxSizeOf.
+                        // and it is independent of the architecture.
+                        core::mem::size_of::<Self>().hash(hasher);
+                        // Hash in representation data.
+                        #(
+                            #repr.hash(hasher);
+                        )*
+                        // Recurse on all fields, which all start
+                        // at the offset of the union.
+                        let old_offset_of = *offset_of;
+                        #(
+                            *offset_of = old_offset_of;
+                            <#fields_types as epserde::traits::ReprHash>::repr_hash(
+                                hasher,
+                                offset_of,
+                            );
+                        )*
+                        // The union might be larger than all its fields.
+                        *offset_of = old_offset_of + core::mem::size_of::<Self>();
+                    }
+                }
+
+                impl<#generics_maxsizeof> epserde::traits::MaxSizeOf for #name<#generics_names> #where_clause{
+                    #[inline(always)]
+                    fn max_size_of() -> usize {
+                        let mut max_size_of = ::core::mem::align_of::<Self>();
+                        // Recurse on all fields.
+                        #(
+                            if max_size_of < <#fields_types as epserde::traits::MaxSizeOf>::max_size_of() {
+                                max_size_of = <#fields_types as epserde::traits::MaxSizeOf>::max_size_of();
+                            }
+                        )*
+                        max_size_of
+                    }
+                }
+            }
+        }
     };
     out.into()
 }
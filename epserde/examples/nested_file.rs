@@ -39,13 +39,14 @@ fn main() {
     };
     // Create an aligned vector to serialize into so we can do an ε-copy
     // deserialization safely
-    let mut file = std::fs::File::create("test.bin").unwrap();
+    let path = std::env::temp_dir().join("epserde_nested_file.bin");
+    let mut file = std::fs::File::create(&path).unwrap();
     // Serialize
     let _bytes_written = s.serialize(&mut file).unwrap();
 
     drop(file);
 
-    let mut file = std::fs::File::open("test.bin").unwrap();
+    let mut file = std::fs::File::open(&path).unwrap();
 
     // Do a full-copy deserialization
 
@@ -60,7 +61,7 @@ fn main() {
     println!();
 
     // Do an ε-copy deserialization
-    let file = std::fs::read("test.bin").unwrap();
+    let file = std::fs::read(&path).unwrap();
     let eps = Struct::deserialize_eps(&file).unwrap();
     println!(
         "ε-copy deserialization type: {}",
@@ -71,4 +72,6 @@ fn main() {
     assert_eq!(s.b.a, eps.b.a);
     assert_eq!(s.b.b, eps.b.b);
     assert_eq!(s.test, eps.test);
+
+    std::fs::remove_file(&path).unwrap();
 }
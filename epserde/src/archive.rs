@@ -0,0 +1,388 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Inria
+ * SPDX-FileCopyrightText: 2025 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+/*!
+
+Archives containing several named structures.
+
+An archive is a file containing several named, independently typed ε-serde
+serializations (the _entries_), each starting at a page boundary, followed by a
+table of contents. Entries are written by an [`ArchiveWriter`], and they are
+retrieved by an [`Archive`], which loads or maps the file once and returns
+for each entry a [`MemCase`] sharing the same memory. As each entry is a
+complete serialization, its header is checked when it is retrieved, so
+retrieving an entry with the wrong type causes a
+[type-hash error](deser::Error::WrongTypeHash).
+
+```
+use epserde::archive::*;
+
+# fn main() -> anyhow::Result<()> {
+let mut writer = ArchiveWriter::new(Vec::new())?;
+writer.add("primes", &vec![2_u64, 3, 5, 7])?;
+writer.add("name", &"first primes".to_string())?;
+let bytes = writer.finish()?;
+
+// Entries must be suitably aligned in memory
+let mut mem = epserde::deser::AlignedMemory::new(bytes.len(), ARCHIVE_ALIGN)?;
+mem.as_bytes_mut().copy_from_slice(&bytes);
+let archive = Archive::from_owned_bytes(mem)?;
+let primes = archive.get::<Vec<u64>>("primes")?;
+assert_eq!(*primes.get(), [2, 3, 5, 7]);
+let name = archive.get::<String>("name")?;
+assert_eq!(*name.get(), "first primes");
+assert!(archive.get::<Vec<u32>>("primes").is_err());
+# Ok(())
+# }
+```
+
+# Format
+
+The file starts with a header containing a magic cookie and the version of
+the format, padded to [`ARCHIVE_ALIGN`] bytes. Entries follow, each aligned to
+[`ARCHIVE_ALIGN`] bytes. Then, the table of contents contains the
+serialization of a `Vec<String>` with the names of the entries and of a
+`Vec<(u64, u64)>` with the offset and length of each entry. Finally, the
+file ends with the offset of the two serializations and the magic cookie,
+all as native-endian `u64`.
+
+*/
+
+use crate::deser::{self, AlignedMemory, Deserialize, LoadError, MemBackend, MemCase, OwnedBytes};
+use crate::deser::{SharedBytes, StableBytes};
+use crate::ser::{self, Serialize, WriteNoStd, WriteWithPos, WriterWithPos};
+use crate::SliceAlignment;
+use std::path::Path;
+use std::sync::Arc;
+
+/// Magic cookie of archives, also used as endianess marker.
+pub const ARCHIVE_MAGIC: u64 = u64::from_ne_bytes(*b"epsarchv");
+/// What we will read if the endianness is mismatched.
+pub const ARCHIVE_MAGIC_REV: u64 = u64::from_le_bytes(ARCHIVE_MAGIC.to_be_bytes());
+/// (Major, Minor) version of the archive format.
+pub const ARCHIVE_VERSION: (u16, u16) = (1, 0);
+/// The alignment of the entries of an archive.
+pub const ARCHIVE_ALIGN: usize = 4096;
+
+pub type Result<T> = core::result::Result<T, Error>;
+
+/// Errors that can happen while reading an [archive](self).
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("The archive is too short ({0} bytes).")]
+    /// The archive is too short to contain a header and a footer.
+    TooShort(usize),
+    #[error("Wrong archive magic cookie 0x{0:016x}. The file is not an ε-serde archive.")]
+    /// The magic cookie is wrong.
+    MagicCookieError(u64),
+    #[error("Archive major version mismatch. Expected {major} but got {0}.", major = ARCHIVE_VERSION.0)]
+    /// The archive was written with an incompatible version of the format.
+    MajorVersionMismatch(u16),
+    #[error("Archive minor version mismatch. Expected {minor} but got {0}.", minor = ARCHIVE_VERSION.1)]
+    /// The archive was written with a compatible, but too new version of
+    /// the format.
+    MinorVersionMismatch(u16),
+    #[error("Wrong archive footer. The archive is probably truncated.")]
+    /// The footer does not end with the magic cookie.
+    FooterError,
+    #[error("Invalid table of contents.")]
+    /// The offsets of the table of contents are out of bounds, or the
+    /// numbers of names and entries differ.
+    InvalidTableOfContents,
+    #[error("The entry {0:?} is out of bounds.")]
+    /// The range of an entry exceeds the space before the table of contents.
+    EntryOutOfBounds(String),
+    #[error("The archive does not contain an entry named {0:?}.")]
+    /// [`Archive::get`] was called with the name of a missing entry.
+    MissingEntry(String),
+    #[error(transparent)]
+    /// Loading the archive or an entry failed.
+    Load(#[from] LoadError),
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Self::Load(e.into())
+    }
+}
+
+impl From<core::alloc::LayoutError> for Error {
+    fn from(e: core::alloc::LayoutError) -> Self {
+        Self::Load(e.into())
+    }
+}
+
+#[cfg(feature = "mmap")]
+impl From<mmap_rs::Error> for Error {
+    fn from(e: mmap_rs::Error) -> Self {
+        Self::Load(e.into())
+    }
+}
+
+impl From<deser::Error> for Error {
+    fn from(e: deser::Error) -> Self {
+        Self::Load(e.into())
+    }
+}
+
+/// The length of the header (before padding).
+const HEADER_LEN: usize = 12;
+/// The length of the footer.
+const FOOTER_LEN: usize = 24;
+
+/// A writer of [archives](self).
+///
+/// Entries are added with [`ArchiveWriter::add`]; the table of contents is
+/// written by [`ArchiveWriter::finish`], which must be called to obtain a valid
+/// archive.
+#[derive(Debug)]
+pub struct ArchiveWriter<W: WriteNoStd> {
+    /// What we actually write on.
+    backend: W,
+    /// How many bytes we have written from the start.
+    pos: usize,
+    /// The names of the entries written so far.
+    names: Vec<String>,
+    /// The offsets and lengths of the entries written so far.
+    ranges: Vec<(u64, u64)>,
+    /// The alignment of large zero-copy slices in the entries.
+    slice_alignment: SliceAlignment,
+}
+
+impl ArchiveWriter<std::io::BufWriter<std::fs::File>> {
+    /// Create a file and return an [`ArchiveWriter`] on it.
+    pub fn create(path: impl AsRef<Path>) -> ser::Result<Self> {
+        let file = std::fs::File::create(path).map_err(ser::Error::FileOpenError)?;
+        Self::new(std::io::BufWriter::new(file))
+    }
+}
+
+impl<W: WriteNoStd> ArchiveWriter<W> {
+    /// Create a new [`ArchiveWriter`] on top of a generic [`WriteNoStd`],
+    /// writing the header of the archive.
+    pub fn new(backend: W) -> ser::Result<Self> {
+        let mut writer = Self {
+            backend,
+            pos: 0,
+            names: vec![],
+            ranges: vec![],
+            slice_alignment: SliceAlignment::NONE,
+        };
+        writer.write_all(&ARCHIVE_MAGIC.to_ne_bytes())?;
+        writer.write_all(&ARCHIVE_VERSION.0.to_ne_bytes())?;
+        writer.write_all(&ARCHIVE_VERSION.1.to_ne_bytes())?;
+        debug_assert_eq!(writer.pos, HEADER_LEN);
+        Ok(writer)
+    }
+
+    /// Set the [alignment of large zero-copy slices](SliceAlignment) in
+    /// the entries written from now on.
+    pub fn with_slice_alignment(mut self, slice_alignment: SliceAlignment) -> Self {
+        self.slice_alignment = slice_alignment;
+        self
+    }
+
+    fn write_all(&mut self, buf: &[u8]) -> ser::Result<()> {
+        self.backend.write_all(buf)?;
+        self.pos += buf.len();
+        Ok(())
+    }
+
+    /// Write zeros up to the next multiple of [`ARCHIVE_ALIGN`].
+    fn pad(&mut self) -> ser::Result<()> {
+        let padding = crate::pad_align_to(self.pos, ARCHIVE_ALIGN);
+        self.write_all(&[0; ARCHIVE_ALIGN][..padding])
+    }
+
+    /// Serialize a value at the next multiple of [`ARCHIVE_ALIGN`], returning
+    /// its offset and length.
+    fn serialize<T: Serialize + ?Sized>(&mut self, value: &T) -> ser::Result<(u64, u64)> {
+        self.pad()?;
+        let offset = self.pos;
+        let mut writer =
+            WriterWithPos::new(&mut self.backend).with_slice_alignment(self.slice_alignment);
+        value.serialize_on_field_write(&mut writer)?;
+        let len = writer.pos();
+        self.pos += len;
+        Ok((offset as u64, len as u64))
+    }
+
+    /// Add an entry with given name to the archive.
+    ///
+    /// If the archive already contains an entry with the same name,
+    /// [`ser::Error::DuplicateEntry`] is returned and nothing is written.
+    pub fn add<T: Serialize + ?Sized>(
+        &mut self,
+        name: impl Into<String>,
+        value: &T,
+    ) -> ser::Result<()> {
+        let name = name.into();
+        if self.names.contains(&name) {
+            return Err(ser::Error::DuplicateEntry(name));
+        }
+        let range = self.serialize(value)?;
+        self.names.push(name);
+        self.ranges.push(range);
+        Ok(())
+    }
+
+    /// Write the table of contents, flush the backend, and return it.
+    pub fn finish(mut self) -> ser::Result<W> {
+        let names = core::mem::take(&mut self.names);
+        let ranges = core::mem::take(&mut self.ranges);
+        let (names_offset, _) = self.serialize(&names)?;
+        let (ranges_offset, _) = self.serialize(&ranges)?;
+        self.write_all(&names_offset.to_ne_bytes())?;
+        self.write_all(&ranges_offset.to_ne_bytes())?;
+        self.write_all(&ARCHIVE_MAGIC.to_ne_bytes())?;
+        self.backend.flush()?;
+        Ok(self.backend)
+    }
+}
+
+/// A reader of [archives](self).
+///
+/// An [`Archive`] loads or maps the file once, and all [`MemCase`] instances
+/// returned by [`Archive::get`] share the same memory, which is released when
+/// the archive and all such instances have been dropped.
+#[derive(Debug)]
+pub struct Archive {
+    /// The content of the archive.
+    bytes: Arc<OwnedBytes>,
+    /// The names of the entries.
+    names: Vec<String>,
+    /// The offsets and lengths of the entries.
+    ranges: Vec<(u64, u64)>,
+}
+
+/// Read a native-endian `u64` at the given offset.
+fn read_u64(bytes: &[u8], offset: usize) -> u64 {
+    u64::from_ne_bytes(bytes[offset..offset + 8].try_into().unwrap())
+}
+
+impl Archive {
+    /// Create an archive from an owner of bytes, reading its
+    /// table of contents.
+    ///
+    /// Entries are ε-copy deserialized from the bytes, so they must be
+    /// aligned to [`ARCHIVE_ALIGN`] (or at least to the largest alignment of
+    /// the zero-copy data in the entries).
+    pub fn from_owned_bytes(bytes: impl StableBytes) -> Result<Self> {
+        let bytes = Arc::new(OwnedBytes::new(bytes));
+        let data = bytes.as_bytes();
+        if data.len() < HEADER_LEN + FOOTER_LEN {
+            return Err(Error::TooShort(data.len()));
+        }
+        match read_u64(data, 0) {
+            ARCHIVE_MAGIC => {}
+            ARCHIVE_MAGIC_REV => return Err(deser::Error::EndiannessError.into()),
+            magic => return Err(Error::MagicCookieError(magic)),
+        }
+        let major = u16::from_ne_bytes(data[8..10].try_into().unwrap());
+        if major != ARCHIVE_VERSION.0 {
+            return Err(Error::MajorVersionMismatch(major));
+        }
+        let minor = u16::from_ne_bytes(data[10..12].try_into().unwrap());
+        if minor > ARCHIVE_VERSION.1 {
+            return Err(Error::MinorVersionMismatch(minor));
+        }
+
+        let footer = data.len() - FOOTER_LEN;
+        if read_u64(data, footer + 16) != ARCHIVE_MAGIC {
+            return Err(Error::FooterError);
+        }
+        let names_offset = read_u64(data, footer) as usize;
+        let ranges_offset = read_u64(data, footer + 8) as usize;
+        if !(names_offset <= ranges_offset && ranges_offset <= footer) {
+            return Err(Error::InvalidTableOfContents);
+        }
+
+        let names = <Vec<String>>::deserialize_full(&mut &data[names_offset..ranges_offset])?;
+        let ranges = <Vec<(u64, u64)>>::deserialize_full(&mut &data[ranges_offset..footer])?;
+        if names.len() != ranges.len() {
+            return Err(Error::InvalidTableOfContents);
+        }
+        for (name, &(offset, len)) in names.iter().zip(&ranges) {
+            if offset
+                .checked_add(len)
+                .is_none_or(|end| end > names_offset as u64)
+            {
+                return Err(Error::EntryOutOfBounds(name.clone()));
+            }
+        }
+
+        Ok(Self {
+            bytes,
+            names,
+            ranges,
+        })
+    }
+
+    /// Load an archive into heap-allocated memory aligned to [`ARCHIVE_ALIGN`].
+    pub fn load_mem(path: impl AsRef<Path>) -> Result<Self> {
+        let file_len = path.as_ref().metadata()?.len() as usize;
+        let mut file = std::fs::File::open(path)?;
+        let mut mem = AlignedMemory::new(file_len, ARCHIVE_ALIGN)?;
+        std::io::Read::read_exact(&mut file, mem.as_bytes_mut())?;
+        Self::from_owned_bytes(mem)
+    }
+
+    /// Memory map an archive.
+    ///
+    /// The behavior of `mmap()` can be modified by passing some
+    /// [`Flags`](deser::Flags); otherwise, just pass `Flags::empty()`.
+    ///
+    /// Requires the `mmap` feature.
+    #[cfg(feature = "mmap")]
+    pub fn mmap(path: impl AsRef<Path>, flags: deser::Flags) -> Result<Self> {
+        let file_len = path.as_ref().metadata()?.len();
+        let file = std::fs::File::open(path)?;
+        let mmap = unsafe {
+            mmap_rs::MmapOptions::new(file_len as _)?
+                .with_flags(flags.mmap_flags())
+                .with_file(&file, 0)
+                .map()?
+        };
+        Self::from_owned_bytes(mmap)
+    }
+
+    /// Return the number of entries in the archive.
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    /// Return `true` if the archive has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    /// Return an iterator over the names of the entries, in the order in
+    /// which they were added.
+    pub fn names(&self) -> impl ExactSizeIterator<Item = &str> + '_ {
+        self.names.iter().map(String::as_str)
+    }
+
+    /// Return whether the archive contains an entry with the given name.
+    pub fn contains(&self, name: &str) -> bool {
+        self.names.iter().any(|n| n == name)
+    }
+
+    /// ε-copy deserialize the entry with given name, returning a [`MemCase`]
+    /// sharing the memory of the archive.
+    ///
+    /// The header of the entry is checked, so using the wrong type will
+    /// cause a [type-hash error](deser::Error::WrongTypeHash).
+    pub fn get<T: Deserialize>(&self, name: &str) -> Result<MemCase<T>> {
+        let Some(index) = self.names.iter().position(|n| n == name) else {
+            return Err(Error::MissingEntry(name.to_string()));
+        };
+        let (offset, len) = self.ranges[index];
+        let range = offset as usize..(offset + len) as usize;
+        let backend = MemBackend::Shared(SharedBytes::new(self.bytes.clone(), range));
+        Ok(MemCase::from_backend(backend)?)
+    }
+}
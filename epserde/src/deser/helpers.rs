@@ -11,12 +11,52 @@ Helpers for deserialization.
 
 */
 
-use super::SliceWithPos;
 use super::{read::*, DeserializeInner};
+use super::{IndexedSlice, SliceWithPos};
 use crate::deser;
 use crate::traits::*;
 use core::mem::MaybeUninit;
 
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::vec::Vec;
+
+/// Full-copy deserialize a field with given name, adding the name to the
+/// [path of errors](deser::Error::in_field).
+///
+/// This function mirrors [`WriteWithNames::write`](crate::ser::WriteWithNames::write),
+/// and the names should be the same used at serialization time.
+#[inline(always)]
+pub fn deserialize_full_field<T: DeserializeInner>(
+    backend: &mut impl ReadWithPos,
+    field_name: &str,
+) -> deser::Result<T> {
+    T::_deserialize_full_inner(backend).map_err(|e| e.in_field(field_name, backend.pos()))
+}
+
+/// ε-copy deserialize a field with given name, adding the name to the
+/// [path of errors](deser::Error::in_field).
+///
+/// This function mirrors [`WriteWithNames::write`](crate::ser::WriteWithNames::write),
+/// and the names should be the same used at serialization time.
+#[inline(always)]
+pub fn deserialize_eps_field<'a, T: DeserializeInner>(
+    backend: &mut SliceWithPos<'a>,
+    field_name: &str,
+) -> deser::Result<T::DeserType<'a>> {
+    T::_deserialize_eps_inner(backend).map_err(|e| e.in_field(field_name, backend.pos))
+}
+
+/// Read the version of a deep-copy structure written by the derive macro,
+/// checking that it is not greater than `version`, the current version of
+/// the structure.
+pub fn deserialize_version(backend: &mut impl ReadWithPos, version: u16) -> deser::Result<u16> {
+    let ser_version = deserialize_full_field::<u16>(backend, "version")?;
+    if ser_version > version {
+        return Err(deser::Error::UnsupportedVersion(ser_version));
+    }
+    Ok(ser_version)
+}
+
 /// Full-copy deserialize a zero-copy structure.
 pub fn deserialize_full_zero<T: ZeroCopy>(backend: &mut impl ReadWithPos) -> deser::Result<T> {
     backend.align::<T>()?;
@@ -36,11 +76,12 @@ pub fn deserialize_full_zero<T: ZeroCopy>(backend: &mut impl ReadWithPos) -> des
 ///
 /// Note that this method uses a single [`ReadNoStd::read_exact`]
 /// call to read the entire vector.
+#[cfg(feature = "alloc")]
 pub fn deserialize_full_vec_zero<T: DeserializeInner + ZeroCopy>(
     backend: &mut impl ReadWithPos,
 ) -> deser::Result<Vec<T>> {
-    let len = usize::_deserialize_full_inner(backend)?;
-    backend.align::<T>()?;
+    let len = deserialize_full_field::<usize>(backend, "len")?;
+    backend.align_slice::<T>(len)?;
     let mut res = Vec::with_capacity(len);
     // SAFETY: we just allocated this vector so it is safe to set the length.
     // read_exact guarantees that the vector will be filled with data.
@@ -54,13 +95,14 @@ pub fn deserialize_full_vec_zero<T: DeserializeInner + ZeroCopy>(
 }
 
 /// Full-copy deserialize a vector of deep-copy structures.
+#[cfg(feature = "alloc")]
 pub fn deserialize_full_vec_deep<T: DeserializeInner + DeepCopy>(
     backend: &mut impl ReadWithPos,
 ) -> deser::Result<Vec<T>> {
-    let len = usize::_deserialize_full_inner(backend)?;
+    let len = deserialize_full_field::<usize>(backend, "len")?;
     let mut res = Vec::with_capacity(len);
     for _ in 0..len {
-        res.push(T::_deserialize_full_inner(backend)?);
+        res.push(deserialize_full_field::<T>(backend, "item")?);
     }
     Ok(res)
 }
@@ -91,9 +133,9 @@ pub fn deserialize_eps_zero<'a, T: ZeroCopy>(
 pub fn deserialize_eps_slice_zero<'a, T: ZeroCopy>(
     backend: &mut SliceWithPos<'a>,
 ) -> deser::Result<&'a [T]> {
-    let len = usize::_deserialize_full_inner(backend)?;
+    let len = deserialize_full_field::<usize>(backend, "len")?;
     let bytes = len * core::mem::size_of::<T>();
-    backend.align::<T>()?;
+    backend.align_slice::<T>(len)?;
     let (pre, data, after) = unsafe { backend.data[..bytes].align_to::<T>() };
     debug_assert!(pre.is_empty());
     debug_assert!(after.is_empty());
@@ -102,13 +144,37 @@ pub fn deserialize_eps_slice_zero<'a, T: ZeroCopy>(
 }
 
 /// ε-copy deserialize a vector of deep-copy structures.
+#[cfg(feature = "alloc")]
 pub fn deserialize_eps_vec_deep<'a, T: DeepCopy + DeserializeInner>(
     backend: &mut SliceWithPos<'a>,
 ) -> deser::Result<Vec<<T as DeserializeInner>::DeserType<'a>>> {
-    let len = usize::_deserialize_full_inner(backend)?;
+    let len = deserialize_full_field::<usize>(backend, "len")?;
     let mut res = Vec::with_capacity(len);
     for _ in 0..len {
-        res.push(T::_deserialize_eps_inner(backend)?);
+        res.push(deserialize_eps_field::<T>(backend, "item")?);
     }
     Ok(res)
 }
+
+/// Full-copy deserialize a vector of [indexed enums](IndexedEnum).
+#[cfg(feature = "alloc")]
+pub fn deserialize_full_vec_indexed<T: IndexedEnum>(
+    backend: &mut impl ReadWithPos,
+) -> deser::Result<Vec<T>> {
+    let tags = deserialize_full_vec_zero::<T::Tag>(backend)?;
+    let positions = deserialize_full_vec_zero::<usize>(backend)?;
+    let payloads = T::_deserialize_full_payloads(backend)?;
+    let slice = IndexedSlice::<T>::new(&tags, &positions, T::_as_payloads(&payloads))?;
+    Ok(slice.iter().collect())
+}
+
+/// ε-copy deserialize an [indexed slice](IndexedSlice) of [indexed enums](IndexedEnum)
+/// backed by the `data` field of `backend`.
+pub fn deserialize_eps_slice_indexed<'a, T: IndexedEnum>(
+    backend: &mut SliceWithPos<'a>,
+) -> deser::Result<IndexedSlice<'a, T>> {
+    let tags = deserialize_eps_slice_zero::<T::Tag>(backend)?;
+    let positions = deserialize_eps_slice_zero::<usize>(backend)?;
+    let payloads = T::_deserialize_eps_payloads(backend)?;
+    IndexedSlice::new(tags, positions, payloads)
+}
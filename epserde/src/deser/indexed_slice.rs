@@ -0,0 +1,100 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Inria
+ * SPDX-FileCopyrightText: 2025 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+use super::*;
+use core::fmt;
+
+/// The ε-copy deserialization type of vectors and boxed slices of
+/// [indexed enums](IndexedEnum).
+///
+/// Elements are rebuilt on the fly by [`get`](IndexedSlice::get) from their
+/// tag and from a copy of their payload, so access by index requires
+/// constant time.
+///
+/// Tags and positions are checked when the slice is created, so ε-copy
+/// deserialization requires time linear in the number of elements, but
+/// access never fails on corrupted data.
+pub struct IndexedSlice<'a, T: IndexedEnum> {
+    tags: &'a [T::Tag],
+    positions: &'a [usize],
+    payloads: T::Payloads<'a>,
+}
+
+impl<'a, T: IndexedEnum> IndexedSlice<'a, T> {
+    /// Create a new indexed slice, checking that the numbers of tags
+    /// and positions are the same, and that all tags and positions are valid.
+    ///
+    /// This method requires linear time.
+    pub fn new(
+        tags: &'a [T::Tag],
+        positions: &'a [usize],
+        payloads: T::Payloads<'a>,
+    ) -> Result<Self> {
+        if tags.len() != positions.len() {
+            return Err(Error::InvalidIndexedSlice(tags.len().min(positions.len())));
+        }
+        for (i, (&tag, &pos)) in tags.iter().zip(positions).enumerate() {
+            if !T::_check(&payloads, tag, pos)? {
+                return Err(Error::InvalidIndexedSlice(i));
+            }
+        }
+        Ok(Self {
+            tags,
+            positions,
+            payloads,
+        })
+    }
+
+    /// Return the number of elements in the slice.
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.tags.len()
+    }
+
+    /// Return `true` if the slice has no elements.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.tags.is_empty()
+    }
+
+    /// Return the element of given index, or `None` if the index is out of bounds.
+    #[inline(always)]
+    pub fn get(&self, index: usize) -> Option<T> {
+        if index < self.len() {
+            Some(T::_get(
+                &self.payloads,
+                self.tags[index],
+                self.positions[index],
+            ))
+        } else {
+            None
+        }
+    }
+
+    /// Return an iterator over the elements of the slice.
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = T> + 'a {
+        let payloads = self.payloads;
+        self.tags
+            .iter()
+            .zip(self.positions)
+            .map(move |(&tag, &pos)| T::_get(&payloads, tag, pos))
+    }
+}
+
+impl<T: IndexedEnum> Clone for IndexedSlice<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: IndexedEnum> Copy for IndexedSlice<'_, T> {}
+
+impl<T: IndexedEnum + fmt::Debug> fmt::Debug for IndexedSlice<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
@@ -4,10 +4,26 @@
  * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
  */
 
+use crate::deser::{Deserialize, DeserializeInner};
 use bitflags::bitflags;
-use core::{mem::size_of, ops::Deref};
+use core::alloc::{Layout, LayoutError};
+use core::{marker::PhantomData, ops::Deref, ops::Range, ptr::NonNull};
 use maligned::A64;
-use mem_dbg::{MemDbg, MemSize};
+#[cfg(feature = "mem_dbg")]
+use mem_dbg::{MemDbg, MemDbgImpl, MemSize, SizeFlags};
+
+#[cfg(not(feature = "std"))]
+use alloc::{
+    alloc::{alloc_zeroed, dealloc, handle_alloc_error, realloc},
+    boxed::Box,
+    sync::Arc,
+    vec::Vec,
+};
+#[cfg(feature = "std")]
+use std::{
+    alloc::{alloc_zeroed, dealloc, handle_alloc_error, realloc},
+    sync::Arc,
+};
 
 bitflags! {
     /// Flags for [`map`] and [`load_mmap`].
@@ -59,88 +75,617 @@ impl Flags {
     }
 }
 
-/// The [alignment](maligned::Alignment) by the [`Memory`](MemBackend::Memory) variant of [`MemBackend`].
+/// The default [alignment](maligned::Alignment) of the [`Memory`](MemBackend::Memory)
+/// variant of [`MemBackend`], used by [`load_mem`](crate::deser::Deserialize::load_mem).
 pub type MemoryAlignment = A64;
 
+/// A heap-allocated memory region with a given alignment.
+///
+/// This is the backend of the [`Memory`](MemBackend::Memory) variant of
+/// [`MemBackend`]. The alignment is chosen at runtime, so it can be
+/// larger than any alignment provided by [`maligned`] (e.g., 4 KiB pages
+/// or 2 MiB huge pages).
+pub struct AlignedMemory {
+    ptr: NonNull<u8>,
+    layout: Layout,
+}
+
+impl AlignedMemory {
+    /// Allocate a zeroed memory region of `len` bytes aligned to `align`.
+    ///
+    /// # Errors
+    ///
+    /// This method will return an error if `align` is not a power of two,
+    /// or if `len` rounded up to `align` overflows an `isize`.
+    pub fn new(len: usize, align: usize) -> Result<Self, LayoutError> {
+        let layout = Layout::from_size_align(len, align)?;
+        if layout.size() == 0 {
+            return Ok(Self {
+                // SAFETY: the alignment is a nonzero power of two.
+                ptr: unsafe { NonNull::new_unchecked(layout.align() as *mut u8) },
+                layout,
+            });
+        }
+        // SAFETY: the size of the layout is nonzero.
+        let ptr = unsafe { alloc_zeroed(layout) };
+        Ok(Self {
+            ptr: NonNull::new(ptr).unwrap_or_else(|| handle_alloc_error(layout)),
+            layout,
+        })
+    }
+
+    /// Resize the memory region to `len` bytes, keeping its alignment.
+    ///
+    /// The content is preserved up to the minimum of the old and new length,
+    /// and new bytes are zeroed.
+    ///
+    /// # Errors
+    ///
+    /// This method will return an error if `len` rounded up to the
+    /// alignment overflows an `isize`.
+    pub fn resize(&mut self, len: usize) -> Result<(), LayoutError> {
+        let layout = Layout::from_size_align(len, self.layout.align())?;
+        if self.layout.size() == 0 || layout.size() == 0 {
+            let mut mem = Self::new(len, self.layout.align())?;
+            let common = self.layout.size().min(len);
+            mem.as_bytes_mut()[..common].copy_from_slice(&self.as_bytes()[..common]);
+            *self = mem;
+            return Ok(());
+        }
+        // SAFETY: the region has been allocated with this layout, and the new
+        // size is nonzero and does not overflow when rounded to the alignment.
+        let ptr = unsafe { realloc(self.ptr.as_ptr(), self.layout, len) };
+        self.ptr = NonNull::new(ptr).unwrap_or_else(|| handle_alloc_error(layout));
+        let old_len = self.layout.size();
+        self.layout = layout;
+        if len > old_len {
+            self.as_bytes_mut()[old_len..].fill(0);
+        }
+        Ok(())
+    }
+
+    /// Return the alignment of the memory region.
+    pub fn align(&self) -> usize {
+        self.layout.align()
+    }
+
+    /// Return the content of the memory region.
+    pub fn as_bytes(&self) -> &[u8] {
+        // SAFETY: the region has been allocated with this layout and initialized.
+        unsafe { core::slice::from_raw_parts(self.ptr.as_ptr(), self.layout.size()) }
+    }
+
+    /// Return the content of the memory region mutably.
+    pub fn as_bytes_mut(&mut self) -> &mut [u8] {
+        // SAFETY: the region has been allocated with this layout and initialized.
+        unsafe { core::slice::from_raw_parts_mut(self.ptr.as_ptr(), self.layout.size()) }
+    }
+}
+
+impl Drop for AlignedMemory {
+    fn drop(&mut self) {
+        if self.layout.size() != 0 {
+            // SAFETY: the region has been allocated with this layout.
+            unsafe { dealloc(self.ptr.as_ptr(), self.layout) }
+        }
+    }
+}
+
+// SAFETY: the memory region is owned and never aliased mutably.
+unsafe impl Send for AlignedMemory {}
+unsafe impl Sync for AlignedMemory {}
+
+impl Deref for AlignedMemory {
+    type Target = [u8];
+    #[inline(always)]
+    fn deref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl core::fmt::Debug for AlignedMemory {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("AlignedMemory")
+            .field("len", &self.layout.size())
+            .field("align", &self.layout.align())
+            .finish()
+    }
+}
+
+#[cfg(feature = "mem_dbg")]
+impl MemSize for AlignedMemory {
+    fn mem_size(&self, _flags: SizeFlags) -> usize {
+        core::mem::size_of::<Self>() + self.layout.size()
+    }
+}
+
+#[cfg(feature = "mem_dbg")]
+impl MemDbgImpl for AlignedMemory {}
+
+/// An owner of bytes whose address does not change when the owner is moved.
+///
+/// This trait is similar to `StableDeref` from the
+/// [`stable_deref_trait`](https://crates.io/crates/stable_deref_trait) crate: it
+/// is used by [`MemCase::from_owned_bytes`] to ε-copy deserialize a structure
+/// from the bytes and then store the owner in the [`MemCase`] as an
+/// [`Owned`](MemBackend::Owned) backend.
+///
+/// It is implemented for [`Vec<u8>`], `Box<[u8]>`, `Arc<[u8]>`, `&'static [u8]`, [`AlignedMemory`],
+/// `mmap_rs::Mmap` (with the `mmap` feature), and `bytes::Bytes` (with the
+/// `bytes` feature).
+///
+/// # Safety
+///
+/// The slice returned by [`Deref::deref`] must always have the same address
+/// and content, even if the owner is moved, until the owner is dropped.
+pub unsafe trait StableBytes: Deref<Target = [u8]> + Send + Sync + 'static {}
+
+unsafe impl StableBytes for Vec<u8> {}
+unsafe impl StableBytes for AlignedMemory {}
+unsafe impl StableBytes for Box<[u8]> {}
+unsafe impl StableBytes for Arc<[u8]> {}
+unsafe impl StableBytes for &'static [u8] {}
+#[cfg(feature = "mmap")]
+unsafe impl StableBytes for mmap_rs::Mmap {}
+#[cfg(feature = "bytes")]
+unsafe impl StableBytes for bytes::Bytes {}
+
+/// A boxed [`StableBytes`] owner.
+///
+/// This is the backend of the [`Owned`](MemBackend::Owned) variant of
+/// [`MemBackend`].
+pub struct OwnedBytes(Box<dyn StableBytes>);
+
+impl OwnedBytes {
+    /// Box the given owner.
+    pub fn new(bytes: impl StableBytes) -> Self {
+        Self(Box::new(bytes))
+    }
+
+    /// Return the owned bytes.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl core::fmt::Debug for OwnedBytes {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("OwnedBytes")
+            .field("len", &self.0.len())
+            .finish()
+    }
+}
+
+#[cfg(feature = "mem_dbg")]
+impl MemSize for OwnedBytes {
+    fn mem_size(&self, _flags: SizeFlags) -> usize {
+        core::mem::size_of::<Self>() + self.0.len()
+    }
+}
+
+#[cfg(feature = "mem_dbg")]
+impl MemDbgImpl for OwnedBytes {}
+
+/// A range of an [`OwnedBytes`] shared by several [`MemCase`] instances.
+///
+/// This is the backend of the [`Shared`](MemBackend::Shared) variant of
+/// [`MemBackend`], which is used, for example, by the entries of an
+/// [archive](crate::archive).
+#[derive(Clone)]
+pub struct SharedBytes {
+    owner: Arc<OwnedBytes>,
+    range: Range<usize>,
+}
+
+impl SharedBytes {
+    /// Create a new shared range of bytes.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if the range is out of the bounds of the owner.
+    pub fn new(owner: Arc<OwnedBytes>, range: Range<usize>) -> Self {
+        assert!(
+            range.start <= range.end && range.end <= owner.as_bytes().len(),
+            "Range {:?} out of bounds for {} bytes",
+            range,
+            owner.as_bytes().len()
+        );
+        Self { owner, range }
+    }
+
+    /// Return the shared bytes.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.owner.as_bytes()[self.range.clone()]
+    }
+}
+
+impl core::fmt::Debug for SharedBytes {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("SharedBytes")
+            .field("range", &self.range)
+            .finish()
+    }
+}
+
+#[cfg(feature = "mem_dbg")]
+impl MemSize for SharedBytes {
+    fn mem_size(&self, _flags: SizeFlags) -> usize {
+        // The owner is shared, so we do not count it
+        core::mem::size_of::<Self>()
+    }
+}
+
+#[cfg(feature = "mem_dbg")]
+impl MemDbgImpl for SharedBytes {}
+
 /// Possible backends of a [`MemCase`]. The `None` variant is used when the data structure is
 /// created in memory; the `Memory` variant is used when the data structure is deserialized
 /// from a file loaded into a heap-allocated memory region; the `Mmap` variant is used when
 /// the data structure is deserialized from a `mmap()`-based region, either coming from
 /// an allocation or a from mapping a file.
-#[derive(Debug, MemDbg, MemSize)]
+#[derive(Debug)]
+#[cfg_attr(feature = "mem_dbg", derive(MemDbg, MemSize))]
 pub enum MemBackend {
     /// No backend. The data structure is a standard Rust data structure.
     /// This variant is returned by [`MemCase::encase`].
     None,
-    /// The backend is a heap-allocated in a memory region aligned to 16 bytes.
-    /// This variant is returned by [`crate::deser::Deserialize::load_mem`].
-    Memory(Box<[MemoryAlignment]>),
+    /// The backend is a heap-allocated memory region with a given alignment.
+    /// This variant is returned by [`crate::deser::Deserialize::load_mem`]
+    /// and [`crate::deser::Deserialize::load_mem_aligned`].
+    Memory(AlignedMemory),
+    /// The backend is an owner of bytes provided by the user.
+    /// This variant is returned by [`MemCase::from_owned_bytes`].
+    Owned(OwnedBytes),
+    /// The backend is a range of bytes shared with other instances.
+    /// This variant is returned by [`crate::archive::Archive::get`].
+    Shared(SharedBytes),
     /// The backend is the result to a call to `mmap()`.
-    /// This variant is returned by [`crate::deser::Deserialize::load_mmap`] and [`crate::deser::Deserialize::mmap`].
+    /// This variant is returned by [`crate::deser::Deserialize::load_mmap`],
+    /// [`crate::deser::Deserialize::mmap`], and [`MemCase::from_mmap`].
     #[cfg(feature = "mmap")]
     Mmap(mmap_rs::Mmap),
+    /// The backend is the result to a call to `mmap()` on a page-aligned range
+    /// of a file containing the data starting at a given offset.
+    /// This variant is returned by [`crate::deser::Deserialize::mmap_range`].
+    #[cfg(feature = "mmap")]
+    MmapRange {
+        /// The memory mapping.
+        mmap: mmap_rs::Mmap,
+        /// The offset of the data in the mapping.
+        offset: usize,
+    },
 }
 
 impl MemBackend {
     pub fn as_ref(&self) -> Option<&[u8]> {
         match self {
             MemBackend::None => None,
-            MemBackend::Memory(mem) => Some(unsafe {
-                core::slice::from_raw_parts(
-                    mem.as_ptr() as *const MemoryAlignment as *const u8,
-                    mem.len() * size_of::<MemoryAlignment>(),
-                )
-            }),
+            MemBackend::Memory(mem) => Some(mem.as_bytes()),
+            MemBackend::Owned(bytes) => Some(bytes.as_bytes()),
+            MemBackend::Shared(bytes) => Some(bytes.as_bytes()),
             #[cfg(feature = "mmap")]
             MemBackend::Mmap(mmap) => Some(mmap),
+            #[cfg(feature = "mmap")]
+            MemBackend::MmapRange { mmap, offset } => Some(&mmap[*offset..]),
         }
     }
 }
 
+/// A family of types parametrized by a lifetime.
+///
+/// This trait is similar to `Yokeable` from the
+/// [`yoke`](https://crates.io/crates/yoke) crate: a [`MemCase`] is
+/// parametrized by a family `F`, and contains a value of type `F::Of<'a>`
+/// whose lifetime is tied to the memory of the [`MemCase`].
+///
+/// All types implementing [`DeserializeInner`] (whose safety contract
+/// requires covariance) are families whose types are
+/// their [ε-copy deserialization types](DeserializeInner::DeserType): in this
+/// way, a [`MemCase<T>`] contains the ε-copy deserialization of a `T`.
+/// Moreover, [`RefFamily<T>`] is the family of references to `T`. Other families
+/// can be implemented to [map](MemCase::map) a [`MemCase`] to sub-structures.
+///
+/// # Safety
+///
+/// `Of<'a>` must be covariant in `'a`, that is, it must be possible to
+/// turn safely a `&'b Of<'a>` into a `&'b Of<'b>` whenever `'a: 'b`:
+/// [`MemCase::get`] relies on this property to tie the lifetime of the
+/// structure to a borrow of the [`MemCase`]. This is true of all types
+/// containing references, slices, and other covariant types, but it is
+/// not true, for example, of types containing a `Cell<&'a T>` or a
+/// `fn(&'a T)`.
+pub unsafe trait LifetimeFamily {
+    /// The type of the family with lifetime `'a`.
+    type Of<'a>;
+}
+
+// SAFETY: deserialization types are covariant by the safety contract of
+// DeserializeInner
+unsafe impl<T: DeserializeInner> LifetimeFamily for T {
+    type Of<'a> = T::DeserType<'a>;
+}
+
+/// The [family](LifetimeFamily) of references `&'a T`.
+///
+/// For example, `RefFamily<[usize]>` can be used to
+/// [map](MemCase::map) a [`MemCase`] to a slice.
+pub struct RefFamily<T: ?Sized>(PhantomData<T>);
+
+// SAFETY: references are covariant in their lifetime
+unsafe impl<T: ?Sized + 'static> LifetimeFamily for RefFamily<T> {
+    type Of<'a> = &'a T;
+}
+
 /// A wrapper keeping together an immutable structure and the memory
 /// it was deserialized from. [`MemCase`] instances can not be cloned, but references
-/// to such instances can be shared freely.
+/// to such instances can be shared freely. Moreover, a [`MemCase`] can be
+/// turned into a cloneable [`SharedMemCase`] using [`MemCase::into_shared`].
 ///
-/// [`MemCase`] implements [`Deref`] and [`AsRef`] to the
-/// wrapped type, so it can be used almost transparently and
-/// with no performance cost. However,
-/// if you need to use a memory-mapped structure as a field in
+/// A [`MemCase`] is parametrized by a [family](LifetimeFamily) `F` and contains
+/// a structure of type `F::Of<'static>`; usually, `F` is the type that was
+/// serialized, and thus the structure is its
+/// [ε-copy deserialization type](DeserializeInner::DeserType).
+///
+/// The structure can be accessed with no performance cost using
+/// [`MemCase::get`], which returns a reference to the structure whose
+/// lifetime is tied to a borrow of the [`MemCase`]: in this way, no
+/// reference to the memory of the backend can outlive the [`MemCase`].
+/// If you need to use a memory-mapped structure as a field in
 /// a struct and you want to avoid `dyn`, you will have
 /// to use [`MemCase`] as the type of the field.
-/// [`MemCase`] implements [`From`] for the
-/// wrapped type, using the no-op [`None`](`MemBackend#variant.None`) variant
-/// of [`MemBackend`], so a structure can be [encased](MemCase::encase)
-/// almost transparently.
-#[derive(Debug, MemDbg, MemSize)]
-pub struct MemCase<S>(pub(crate) S, pub(crate) MemBackend);
-
-impl<S> MemCase<S> {
+/// A structure can be [encased](MemCase::encase) in a [`MemCase`]
+/// using the no-op [`None`](`MemBackend#variant.None`) variant
+/// of [`MemBackend`].
+pub struct MemCase<F: LifetimeFamily>(pub(crate) F::Of<'static>, pub(crate) MemBackend);
+
+impl<F: LifetimeFamily> MemCase<F> {
     /// Encases a data structure in a [`MemCase`] with no backend.
-    pub fn encase(s: S) -> MemCase<S> {
+    pub fn encase(s: F::Of<'static>) -> Self {
         MemCase(s, MemBackend::None)
     }
+
+    /// Return a reference to the structure.
+    ///
+    /// The lifetime of the structure is that of the borrow of `self`, so
+    /// references it contains, such as ε-copy deserialized slices, can not
+    /// outlive the [`MemCase`]:
+    ///
+    /// ```compile_fail
+    /// use epserde::prelude::*;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut bytes = Vec::new();
+    /// vec![1_u32, 2, 3].serialize(&mut bytes)?;
+    /// let mut mem = epserde::deser::AlignedMemory::new(bytes.len(), 16)?;
+    /// mem.as_bytes_mut().copy_from_slice(&bytes);
+    ///
+    /// let case = MemCase::<Vec<u32>>::from_owned_bytes(mem)?;
+    /// let slice: &[u32] = case.get();
+    /// drop(case);
+    /// assert_eq!(slice, [1, 2, 3]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline(always)]
+    pub fn get(&self) -> &F::Of<'_> {
+        // SAFETY: F::Of is covariant, and the backend lives as long as self
+        unsafe { &*core::ptr::from_ref(&self.0).cast::<F::Of<'_>>() }
+    }
+
+    /// Turn this [`MemCase`] into a [`SharedMemCase`], which can be cloned if
+    /// the structure can be cloned.
+    pub fn into_shared(self) -> SharedMemCase<F> {
+        SharedMemCase(self.0, Arc::new(self.1))
+    }
+
+    /// Return a [`MemCase`] containing the result of applying `f` to
+    /// the structure, and the same backend.
+    ///
+    /// This method makes it possible to derive a [`MemCase`] of a
+    /// sub-structure, such as a field of an ε-copy deserialized structure,
+    /// which can be then stored in long-lived structures. The resulting type
+    /// is specified by a [family](LifetimeFamily) `G`, and since `f` must work
+    /// for every lifetime `'a`, its result can only borrow from the memory
+    /// of the backend through the structure. The second argument of `f`
+    /// is a marker making the lifetime explicit, and can be ignored.
+    ///
+    /// ```
+    /// use epserde::prelude::*;
+    /// use epserde::deser::RefFamily;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut bytes = Vec::new();
+    /// vec![1_u32, 2, 3, 4].serialize(&mut bytes)?;
+    /// let mut mem = epserde::deser::AlignedMemory::new(bytes.len(), 16)?;
+    /// mem.as_bytes_mut().copy_from_slice(&bytes);
+    ///
+    /// let v = MemCase::<Vec<u32>>::from_owned_bytes(mem)?;
+    /// // The tail of the vector, sharing the same memory
+    /// let tail: MemCase<RefFamily<[u32]>> = v.map(|s, _| &s[2..]);
+    /// assert_eq!(*tail.get(), [3, 4]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn map<G: LifetimeFamily>(
+        self,
+        f: impl for<'a> FnOnce(F::Of<'a>, PhantomData<&'a ()>) -> G::Of<'a>,
+    ) -> MemCase<G> {
+        MemCase(f(self.0, PhantomData), self.1)
+    }
+
+    /// An alias of [`MemCase::map`].
+    #[inline(always)]
+    pub fn project<G: LifetimeFamily>(
+        self,
+        f: impl for<'a> FnOnce(F::Of<'a>, PhantomData<&'a ()>) -> G::Of<'a>,
+    ) -> MemCase<G> {
+        self.map(f)
+    }
 }
 
-unsafe impl<S: Send> Send for MemCase<S> {}
-unsafe impl<S: Sync> Sync for MemCase<S> {}
+impl<T: Deserialize> MemCase<T> {
+    /// ε-copy deserialize a structure of type `T` from the given owner of
+    /// bytes, returning a [`MemCase`] containing the structure and the owner.
+    ///
+    /// No copy is performed, so the bytes must be suitably aligned for the
+    /// zero-copy data they contain: otherwise, an
+    /// [alignment error](crate::deser::Error::AlignmentError) is returned.
+    /// Note that a [`Vec<u8>`] is not guaranteed to be aligned beyond one byte.
+    pub fn from_owned_bytes(bytes: impl StableBytes) -> crate::deser::Result<Self> {
+        Self::from_backend(MemBackend::Owned(OwnedBytes::new(bytes)))
+    }
 
-impl<S> Deref for MemCase<S> {
-    type Target = S;
+    /// ε-copy deserialize a structure of type `T` from a read-only memory
+    /// mapping, such as the one returned by
+    /// [`Serialize::store_mmap`](crate::ser::Serialize::store_mmap),
+    /// returning a [`MemCase`] containing the structure and the mapping.
+    ///
+    /// Requires the `mmap` feature.
+    #[cfg(feature = "mmap")]
+    pub fn from_mmap(mmap: mmap_rs::Mmap) -> crate::deser::Result<Self> {
+        Self::from_backend(MemBackend::Mmap(mmap))
+    }
+
+    /// ε-copy deserialize a structure of type `T` from the bytes of
+    /// a backend, returning a [`MemCase`] containing the structure and the backend.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if the backend is [`MemBackend::None`].
+    pub(crate) fn from_backend(backend: MemBackend) -> crate::deser::Result<Self> {
+        let bytes = backend.as_ref().unwrap();
+        // SAFETY: the address of the bytes of a backend does not change when
+        // the backend is moved, and the backend is dropped after the structure.
+        let data = unsafe { core::slice::from_raw_parts(bytes.as_ptr(), bytes.len()) };
+        let s = T::deserialize_eps(data)?;
+        Ok(MemCase(s, backend))
+    }
+}
+
+unsafe impl<F: LifetimeFamily> Send for MemCase<F> where F::Of<'static>: Send {}
+unsafe impl<F: LifetimeFamily> Sync for MemCase<F> where F::Of<'static>: Sync {}
+
+impl<F: LifetimeFamily> core::fmt::Debug for MemCase<F>
+where
+    F::Of<'static>: core::fmt::Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("MemCase")
+            .field(&self.0)
+            .field(&self.1)
+            .finish()
+    }
+}
+
+#[cfg(feature = "mem_dbg")]
+impl<F: LifetimeFamily> MemSize for MemCase<F>
+where
+    F::Of<'static>: MemSize,
+{
+    fn mem_size(&self, flags: SizeFlags) -> usize {
+        core::mem::size_of::<Self>() - core::mem::size_of::<F::Of<'static>>()
+            + self.0.mem_size(flags)
+            - core::mem::size_of::<MemBackend>()
+            + self.1.mem_size(flags)
+    }
+}
+
+#[cfg(feature = "mem_dbg")]
+impl<F: LifetimeFamily> MemDbgImpl for MemCase<F> where F::Of<'static>: MemDbgImpl {}
+
+/// A cloneable version of [`MemCase`], whose backend is shared using an [`Arc`].
+///
+/// Instances are created by [`MemCase::into_shared`], and they can be cloned
+/// if the structure can be cloned, which is usually the case for ε-copy
+/// deserialized structures, as they contain mostly references. In this way, the
+/// same structure can be handed to several threads as an owned value, and
+/// several structures can share the same backend using
+/// [`SharedMemCase::map`].
+pub struct SharedMemCase<F: LifetimeFamily>(F::Of<'static>, Arc<MemBackend>);
+
+impl<F: LifetimeFamily> SharedMemCase<F>
+where
+    F::Of<'static>: Clone,
+{
+    /// Return a [`SharedMemCase`] containing the result of applying `f` to
+    /// a clone of the structure and sharing the same backend.
+    ///
+    /// See [`MemCase::map`] for more information.
+    pub fn map<G: LifetimeFamily>(
+        &self,
+        f: impl for<'a> FnOnce(F::Of<'a>, PhantomData<&'a ()>) -> G::Of<'a>,
+    ) -> SharedMemCase<G> {
+        SharedMemCase(f(self.0.clone(), PhantomData), self.1.clone())
+    }
+
+    /// An alias of [`SharedMemCase::map`].
     #[inline(always)]
-    fn deref(&self) -> &Self::Target {
-        &self.0
+    pub fn project<G: LifetimeFamily>(
+        &self,
+        f: impl for<'a> FnOnce(F::Of<'a>, PhantomData<&'a ()>) -> G::Of<'a>,
+    ) -> SharedMemCase<G> {
+        self.map(f)
     }
 }
 
-impl<S> AsRef<S> for MemCase<S> {
+impl<F: LifetimeFamily> SharedMemCase<F> {
+    /// Return a reference to the structure.
+    ///
+    /// See [`MemCase::get`] for more information.
     #[inline(always)]
-    fn as_ref(&self) -> &S {
-        &self.0
+    pub fn get(&self) -> &F::Of<'_> {
+        // SAFETY: F::Of is covariant, and the backend lives as long as self
+        unsafe { &*core::ptr::from_ref(&self.0).cast::<F::Of<'_>>() }
+    }
+
+    /// Return the number of [`SharedMemCase`] instances sharing the backend
+    /// of this instance.
+    pub fn strong_count(&self) -> usize {
+        Arc::strong_count(&self.1)
+    }
+}
+
+impl<F: LifetimeFamily> Clone for SharedMemCase<F>
+where
+    F::Of<'static>: Clone,
+{
+    fn clone(&self) -> Self {
+        SharedMemCase(self.0.clone(), self.1.clone())
     }
 }
 
-impl<S: Send + Sync> From<S> for MemCase<S> {
-    fn from(s: S) -> Self {
-        MemCase::encase(s)
+unsafe impl<F: LifetimeFamily> Send for SharedMemCase<F> where F::Of<'static>: Send {}
+unsafe impl<F: LifetimeFamily> Sync for SharedMemCase<F> where F::Of<'static>: Sync {}
+
+impl<F: LifetimeFamily> From<MemCase<F>> for SharedMemCase<F> {
+    fn from(mem_case: MemCase<F>) -> Self {
+        mem_case.into_shared()
+    }
+}
+
+impl<F: LifetimeFamily> core::fmt::Debug for SharedMemCase<F>
+where
+    F::Of<'static>: core::fmt::Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("SharedMemCase")
+            .field(&self.0)
+            .field(&self.1)
+            .finish()
     }
 }
+
+#[cfg(feature = "mem_dbg")]
+impl<F: LifetimeFamily> MemSize for SharedMemCase<F>
+where
+    F::Of<'static>: MemSize,
+{
+    fn mem_size(&self, flags: SizeFlags) -> usize {
+        // The backend is shared, so we do not count it
+        core::mem::size_of::<Self>() - core::mem::size_of::<F::Of<'static>>()
+            + self.0.mem_size(flags)
+    }
+}
+
+#[cfg(feature = "mem_dbg")]
+impl<F: LifetimeFamily> MemDbgImpl for SharedMemCase<F> where F::Of<'static>: MemDbgImpl {}
@@ -18,15 +18,26 @@ which is automatically derived with `#[derive(Deserialize)]`.
 */
 
 use crate::traits::*;
-use crate::{MAGIC, MAGIC_REV, VERSION};
+use crate::{SliceAlignment, MAGIC, MAGIC_REV, VERSION};
+use core::hash::Hasher;
+#[cfg(feature = "std")]
 use core::mem::align_of;
-use core::ptr::addr_of_mut;
-use core::{hash::Hasher, mem::MaybeUninit};
+#[cfg(feature = "std")]
 use std::{io::BufReader, path::Path};
 
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::{
+    boxed::Box,
+    string::{String, ToString},
+};
+
 pub mod helpers;
 pub use helpers::*;
+pub mod indexed_slice;
+pub use indexed_slice::*;
+#[cfg(feature = "alloc")]
 pub mod mem_case;
+#[cfg(feature = "alloc")]
 pub use mem_case::*;
 pub mod read;
 pub use read::*;
@@ -47,6 +58,7 @@ pub type DeserType<'a, T> = <T as DeserializeInner>::DeserType<'a>;
 /// It provides several convenience methods to load or map into memory
 /// structures that have been previously serialized. See, for example,
 /// [`Deserialize::load_full`], [`Deserialize::load_mem`], and [`Deserialize::mmap`].
+/// Such methods return a [`LoadError`], which wraps deserialization errors.
 pub trait Deserialize: TypeHash + ReprHash + DeserializeInner {
     /// Fully deserialize a structure of this type from the given backend.
     fn deserialize_full(backend: &mut impl ReadNoStd) -> Result<Self>;
@@ -54,10 +66,103 @@ pub trait Deserialize: TypeHash + ReprHash + DeserializeInner {
     fn deserialize_eps(backend: &'_ [u8]) -> Result<Self::DeserType<'_>>;
 
     /// Convenience method to fully deserialize from a file.
-    fn load_full(path: impl AsRef<Path>) -> anyhow::Result<Self> {
-        let file = std::fs::File::open(path).map_err(Error::FileOpenError)?;
+    #[cfg(feature = "std")]
+    fn load_full(path: impl AsRef<Path>) -> core::result::Result<Self, LoadError> {
+        let file = std::fs::File::open(path)?;
         let mut buf_reader = BufReader::new(file);
-        Self::deserialize_full(&mut buf_reader).map_err(|e| e.into())
+        Ok(Self::deserialize_full(&mut buf_reader)?)
+    }
+
+    /// Fully deserialize a structure of this type from a Tokio
+    /// [`AsyncRead`](tokio::io::AsyncRead).
+    ///
+    /// The data is the same accepted by [`Deserialize::deserialize_full`].
+    /// However, since deserialization is synchronous, it is performed in a
+    /// [blocking task](tokio::task::spawn_blocking), which receives the data
+    /// in chunks through a bounded channel, so the additional memory needed
+    /// does not depend on the size of the serialized data. Since the backend
+    /// is read ahead, a few hundred kilobytes of data following the structure
+    /// might be consumed, too. This method must be called within a Tokio
+    /// runtime.
+    ///
+    /// Requires the `async` feature.
+    #[cfg(feature = "async")]
+    fn deserialize_full_async(
+        backend: &mut (impl tokio::io::AsyncRead + Unpin + Send),
+    ) -> impl core::future::Future<Output = Result<Self>> + Send
+    where
+        Self: Send + 'static,
+    {
+        async move {
+            use crate::utils::sync_bridge::*;
+
+            let (sender, receiver) = tokio::sync::mpsc::channel(CHANNEL_CAPACITY);
+            let task = tokio::task::spawn_blocking(move || {
+                Self::deserialize_full(&mut ChannelReader::new(receiver))
+            });
+
+            // Stop when the backend is exhausted, or when the deserialization
+            // ends, dropping the receiver; returning drops the sender, which
+            // stops the deserialization
+            {
+                let mut pos = 0;
+                let mut closed = core::pin::pin!(sender.closed());
+                while let Ok(permit) = sender.reserve().await {
+                    let mut chunk = vec![0; CHUNK_SIZE];
+                    let Some(len) = read_until(backend, &mut chunk, closed.as_mut()).await else {
+                        break;
+                    };
+                    let len = len.map_err(|e| Error::read_error(Some(e)).at_pos(pos))?;
+                    if len == 0 {
+                        break;
+                    }
+                    chunk.truncate(len);
+                    pos += len;
+                    permit.send(chunk);
+                }
+            }
+            drop(sender);
+
+            join(task).await.map_err(|e| Error::read_error(Some(e)))?
+        }
+    }
+
+    /// Asynchronously load a file into heap-allocated memory and ε-deserialize
+    /// a data structure from it, returning a [`MemCase`] containing the data
+    /// structure and the memory. Excess bytes are zeroed out.
+    ///
+    /// This method is the asynchronous version of [`Deserialize::load_mem`]:
+    /// the file is read in a [blocking task](tokio::task::spawn_blocking), and
+    /// this method must be called within a Tokio runtime.
+    ///
+    /// Requires the `async` feature.
+    #[cfg(feature = "async")]
+    fn load_mem_async(
+        path: impl AsRef<Path> + Send,
+    ) -> impl core::future::Future<Output = core::result::Result<MemCase<Self>, LoadError>> + Send
+    {
+        async move {
+            let align_to = align_of::<MemoryAlignment>();
+            if align_of::<Self>() > align_to {
+                return Err(Error::AlignmentError.into());
+            }
+            let path = path.as_ref().to_owned();
+            let task = tokio::task::spawn_blocking(move || {
+                let mut file = std::fs::File::open(path)?;
+                let file_len = file.metadata()?.len() as usize;
+                // Round up to the size of MemoryAlignment
+                let capacity = file_len + crate::pad_align_to(file_len, align_to);
+
+                // The memory is zeroed, so excess bytes guarantee zero-extension
+                // semantics for bit vectors and full-vector initialization.
+                let mut mem = AlignedMemory::new(capacity, align_to)?;
+                std::io::Read::read_exact(&mut file, &mut mem.as_bytes_mut()[..file_len])?;
+                Ok::<_, LoadError>(mem)
+            });
+            let mem = crate::utils::sync_bridge::join(task).await??;
+
+            Ok(MemCase::from_backend(MemBackend::Memory(mem))?)
+        }
     }
 
     /// Load a file into heap-allocated memory and ε-deserialize a data structure from it,
@@ -66,83 +171,137 @@ pub trait Deserialize: TypeHash + ReprHash + DeserializeInner {
     ///
     /// The allocated memory will have [`MemoryAlignment`] as alignment: types with
     /// a higher alignment requirement will cause an [alignment error](`Error::AlignmentError`).
-    fn load_mem<'a>(
+    /// Use [`Deserialize::load_mem_aligned`] to specify a different alignment.
+    #[cfg(feature = "std")]
+    fn load_mem(path: impl AsRef<Path>) -> core::result::Result<MemCase<Self>, LoadError> {
+        Self::load_mem_aligned(path, align_of::<MemoryAlignment>())
+    }
+
+    /// Load a file into heap-allocated memory with alignment `align` and
+    /// ε-deserialize a data structure from it, returning a [`MemCase`]
+    /// containing the data structure and the memory. Excess bytes are zeroed out.
+    ///
+    /// The alignment must be a power of two. Types with a higher alignment
+    /// requirement will cause an [alignment error](`Error::AlignmentError`).
+    /// Large alignments, such as 4 KiB or 2 MiB, are useful in conjunction with
+    /// [slice alignment](crate::SliceAlignment).
+    #[cfg(feature = "std")]
+    fn load_mem_aligned(
         path: impl AsRef<Path>,
-    ) -> anyhow::Result<MemCase<<Self as DeserializeInner>::DeserType<'a>>> {
-        let align_to = align_of::<MemoryAlignment>();
-        if align_of::<Self>() > align_to {
+        align: usize,
+    ) -> core::result::Result<MemCase<Self>, LoadError> {
+        if align_of::<Self>() > align {
             return Err(Error::AlignmentError.into());
         }
         let file_len = path.as_ref().metadata()?.len() as usize;
         let mut file = std::fs::File::open(path)?;
-        // Round up to u128 size
-        let capacity = file_len + crate::pad_align_to(file_len, align_to);
-
-        let mut uninit: MaybeUninit<MemCase<<Self as DeserializeInner>::DeserType<'_>>> =
-            MaybeUninit::uninit();
-        let ptr = uninit.as_mut_ptr();
-
-        // SAFETY: the entire vector will be filled with data read from the file,
-        // or with zeroes if the file is shorter than the vector.
-        #[allow(invalid_value)]
-        let mut aligned_vec = unsafe {
-            <Vec<MemoryAlignment>>::from_raw_parts(
-                std::alloc::alloc(std::alloc::Layout::from_size_align(capacity, align_to)?)
-                    as *mut MemoryAlignment,
-                capacity / align_to,
-                capacity / align_to,
-            )
-        };
+        // Round up to the size of MemoryAlignment
+        let capacity = file_len + crate::pad_align_to(file_len, align_of::<MemoryAlignment>());
 
-        let bytes = unsafe {
-            core::slice::from_raw_parts_mut(aligned_vec.as_mut_ptr() as *mut u8, capacity)
-        };
+        // The memory is zeroed, so excess bytes guarantee zero-extension
+        // semantics for bit vectors and full-vector initialization.
+        let mut mem = AlignedMemory::new(capacity, align)?;
+        file.read_exact(&mut mem.as_bytes_mut()[..file_len])?;
 
-        file.read_exact(&mut bytes[..file_len])?;
-        // Fixes the last few bytes to guarantee zero-extension semantics
-        // for bit vectors and full-vector initialization.
-        bytes[file_len..].fill(0);
+        let backend = MemBackend::Memory(mem);
 
-        // SAFETY: the vector is aligned to 16 bytes.
-        let backend = MemBackend::Memory(aligned_vec.into_boxed_slice());
+        Ok(MemCase::from_backend(backend)?)
+    }
+
+    /// Read data from a [`Read`](std::io::Read) into heap-allocated memory and
+    /// ε-deserialize a data structure from it, returning a [`MemCase`]
+    /// containing the data structure and the memory. Excess bytes are zeroed out.
+    ///
+    /// Data is read until the end of the stream in a buffer whose initial size is
+    /// `len_hint` (plus a spare byte, rounded up to the alignment): it is not
+    /// necessary that the hint is correct, but when it is not smaller than the
+    /// length of the data no reallocation happens, and the buffer is used
+    /// as it is. Otherwise, the buffer is enlarged as needed, and shrunk
+    /// to the length of the data at the end. In this way it is possible to
+    /// ε-copy load, for example, data coming from standard input, a
+    /// decompression stream, or a socket.
+    ///
+    /// The allocated memory will have [`MemoryAlignment`] as alignment: types with
+    /// a higher alignment requirement will cause an [alignment error](`Error::AlignmentError`).
+    #[cfg(feature = "std")]
+    fn read_mem(
+        mut reader: impl std::io::Read,
+        len_hint: usize,
+    ) -> core::result::Result<MemCase<Self>, LoadError> {
+        let align_to = align_of::<MemoryAlignment>();
+        if align_of::<Self>() > align_to {
+            return Err(Error::AlignmentError.into());
+        }
 
-        // store the backend inside the MemCase
-        unsafe {
-            addr_of_mut!((*ptr).1).write(backend);
+        // We need at least one spare byte to detect the end of the stream;
+        // the size of the buffer is always a multiple of MemoryAlignment
+        let size = len_hint.saturating_add(1);
+        let size = size.saturating_add(crate::pad_align_to(size, align_to));
+        let mut mem = AlignedMemory::new(size, align_to)?;
+        let mut len = 0;
+        let mut resized = false;
+        loop {
+            if len == mem.as_bytes().len() {
+                mem.resize(2 * len)?;
+                resized = true;
+            }
+            match reader.read(&mut mem.as_bytes_mut()[len..]) {
+                Ok(0) => break,
+                Ok(n) => len += n,
+                Err(e) if e.kind() == std::io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e.into()),
+            }
         }
-        // deserialize the data structure
-        let mem = unsafe { (*ptr).1.as_ref().unwrap() };
-        let s = Self::deserialize_eps(mem)?;
-        // write the deserialized struct in the memcase
-        unsafe {
-            addr_of_mut!((*ptr).0).write(s);
+        if resized {
+            // Shrink to the length rounded up to the size of MemoryAlignment;
+            // the excess bytes are zero
+            mem.resize(len + crate::pad_align_to(len, align_to))?;
         }
-        // finish init
-        Ok(unsafe { uninit.assume_init() })
+
+        Ok(MemCase::from_backend(MemBackend::Memory(mem))?)
+    }
+
+    /// Load `len` bytes starting at `offset` from a file into heap-allocated
+    /// memory and ε-deserialize a data structure from them, returning a
+    /// [`MemCase`] containing the data structure and the memory. Excess bytes
+    /// are zeroed out.
+    ///
+    /// In this way, a single file can contain several independently loadable
+    /// structures. The allocated memory will have [`MemoryAlignment`] as
+    /// alignment: types with a higher alignment requirement will cause an
+    /// [alignment error](`Error::AlignmentError`).
+    #[cfg(feature = "std")]
+    fn load_mem_range(
+        path: impl AsRef<Path>,
+        offset: u64,
+        len: usize,
+    ) -> core::result::Result<MemCase<Self>, LoadError> {
+        let mut file = std::fs::File::open(path)?;
+        check_range(&file, offset, len)?;
+        std::io::Seek::seek(&mut file, std::io::SeekFrom::Start(offset))?;
+        Self::read_mem(std::io::Read::take(file, len as u64), len)
     }
 
     /// Load a file into `mmap()`-allocated memory and ε-deserialize a data structure from it,
     /// returning a [`MemCase`] containing the data structure and the
     /// memory. Excess bytes are zeroed out.
     ///
+    /// The allocated memory is aligned to a page boundary.
+    ///
     /// The behavior of `mmap()` can be modified by passing some [`Flags`]; otherwise,
     /// just pass `Flags::empty()`.
     ///
     /// Requires the `mmap` feature.
     #[cfg(feature = "mmap")]
     #[allow(clippy::uninit_vec)]
-    fn load_mmap<'a>(
+    fn load_mmap(
         path: impl AsRef<Path>,
         flags: Flags,
-    ) -> anyhow::Result<MemCase<<Self as DeserializeInner>::DeserType<'a>>> {
+    ) -> core::result::Result<MemCase<Self>, LoadError> {
         let file_len = path.as_ref().metadata()?.len() as usize;
         let mut file = std::fs::File::open(path)?;
         let capacity = file_len + crate::pad_align_to(file_len, 16);
 
-        let mut uninit: MaybeUninit<MemCase<<Self as DeserializeInner>::DeserType<'_>>> =
-            MaybeUninit::uninit();
-        let ptr = uninit.as_mut_ptr();
-
         let mut mmap = mmap_rs::MmapOptions::new(capacity)?
             .with_flags(flags.mmap_flags())
             .map_mut()?;
@@ -153,19 +312,7 @@ pub trait Deserialize: TypeHash + ReprHash + DeserializeInner {
 
         let backend = MemBackend::Mmap(mmap.make_read_only().map_err(|(_, err)| err)?);
 
-        // store the backend inside the MemCase
-        unsafe {
-            addr_of_mut!((*ptr).1).write(backend);
-        }
-        // deserialize the data structure
-        let mem = unsafe { (*ptr).1.as_ref().unwrap() };
-        let s = Self::deserialize_eps(mem)?;
-        // write the deserialized struct in the MemCase
-        unsafe {
-            addr_of_mut!((*ptr).0).write(s);
-        }
-        // finish init
-        Ok(unsafe { uninit.assume_init() })
+        Ok(MemCase::from_backend(backend)?)
     }
 
     /// Memory map a file and ε-deserialize a data structure from it,
@@ -178,17 +325,13 @@ pub trait Deserialize: TypeHash + ReprHash + DeserializeInner {
     /// Requires the `mmap` feature.
     #[cfg(feature = "mmap")]
     #[allow(clippy::uninit_vec)]
-    fn mmap<'a>(
+    fn mmap(
         path: impl AsRef<Path>,
         flags: Flags,
-    ) -> anyhow::Result<MemCase<<Self as DeserializeInner>::DeserType<'a>>> {
+    ) -> core::result::Result<MemCase<Self>, LoadError> {
         let file_len = path.as_ref().metadata()?.len();
         let file = std::fs::File::open(path)?;
 
-        let mut uninit: MaybeUninit<MemCase<<Self as DeserializeInner>::DeserType<'_>>> =
-            MaybeUninit::uninit();
-        let ptr = uninit.as_mut_ptr();
-
         let mmap = unsafe {
             mmap_rs::MmapOptions::new(file_len as _)?
                 .with_flags(flags.mmap_flags())
@@ -196,21 +339,78 @@ pub trait Deserialize: TypeHash + ReprHash + DeserializeInner {
                 .map()?
         };
 
-        // store the backend inside the MemCase
-        unsafe {
-            addr_of_mut!((*ptr).1).write(MemBackend::Mmap(mmap));
-        }
+        Ok(MemCase::from_backend(MemBackend::Mmap(mmap))?)
+    }
 
-        let mmap = unsafe { (*ptr).1.as_ref().unwrap() };
-        // deserialize the data structure
-        let s = Self::deserialize_eps(mmap)?;
-        // write the deserialized struct in the MemCase
-        unsafe {
-            addr_of_mut!((*ptr).0).write(s);
-        }
-        // finish init
-        Ok(unsafe { uninit.assume_init() })
+    /// Memory map `len` bytes starting at `offset` from a file and
+    /// ε-deserialize a data structure from them, returning a [`MemCase`]
+    /// containing the data structure and the memory mapping.
+    ///
+    /// In this way, a single file can contain several independently mappable
+    /// structures. The mapping starts at the largest multiple of the
+    /// [allocation granularity](mmap_rs::MmapOptions::allocation_granularity)
+    /// not larger than `offset`, so the address of the data has the same
+    /// alignment as `offset` with respect to a page boundary: since zero-copy
+    /// data is aligned with respect to the start of the serialized data,
+    /// `offset` should be a multiple of [`MemoryAlignment`] (or of the page
+    /// size, if [slice alignment](crate::SliceAlignment) is used), or an
+    /// [alignment error](`Error::AlignmentError`) might be returned.
+    ///
+    /// The behavior of `mmap()` can be modified by passing some [`Flags`]; otherwise,
+    /// just pass `Flags::empty()`.
+    ///
+    /// Requires the `mmap` feature.
+    #[cfg(feature = "mmap")]
+    fn mmap_range(
+        path: impl AsRef<Path>,
+        offset: u64,
+        len: usize,
+        flags: Flags,
+    ) -> core::result::Result<MemCase<Self>, LoadError> {
+        let file = std::fs::File::open(path)?;
+        check_range(&file, offset, len)?;
+        let granularity = mmap_rs::MmapOptions::allocation_granularity() as u64;
+        let delta = (offset % granularity) as usize;
+
+        let mmap = unsafe {
+            mmap_rs::MmapOptions::new(delta + len)?
+                .with_flags(flags.mmap_flags())
+                .with_file(&file, offset - delta as u64)
+                .map()?
+        };
+
+        Ok(MemCase::from_backend(MemBackend::MmapRange {
+            mmap,
+            offset: delta,
+        })?)
+    }
+}
+
+/// Check that a range of bytes is nonempty and contained in a file.
+#[cfg(feature = "std")]
+fn check_range(
+    file: &std::fs::File,
+    offset: u64,
+    len: usize,
+) -> core::result::Result<(), LoadError> {
+    if len == 0 {
+        return Err(LoadError::EmptyRange(offset));
     }
+    let file_len = file.metadata()?.len();
+    if offset
+        .checked_add(len as u64)
+        .is_none_or(|end| end > file_len)
+    {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::UnexpectedEof,
+            format!(
+                "Range of {} bytes at offset {} exceeds the file length {}",
+                len, offset, file_len
+            ),
+        )
+        .into());
+    }
+    Ok(())
 }
 
 /// Inner trait to implement deserialization of a type. This trait exists
@@ -222,7 +422,37 @@ pub trait Deserialize: TypeHash + ReprHash + DeserializeInner {
 /// the user from modifying the methods in [`Deserialize`].
 ///
 /// The user should not implement this trait directly, but rather derive it.
-pub trait DeserializeInner: Sized {
+///
+/// # Safety
+///
+/// The [deserialization type](DeserializeInner::DeserType) must be covariant
+/// in `'a`, as [`MemCase::get`] relies on this property (see
+/// [`LifetimeFamily`]). The derive macro checks this property at compile time.
+///
+/// For example, the following implementation, whose deserialization type is
+/// invariant, does not compile without an `unsafe` marker:
+///
+/// ```compile_fail,E0200
+/// use core::cell::Cell;
+/// use epserde::deser::{self, DeserializeInner, ReadWithPos, SliceWithPos};
+///
+/// struct Invariant;
+///
+/// impl DeserializeInner for Invariant {
+///     type DeserType<'a> = Cell<&'a str>;
+///
+///     fn _deserialize_full_inner(_backend: &mut impl ReadWithPos) -> deser::Result<Self> {
+///         Ok(Invariant)
+///     }
+///
+///     fn _deserialize_eps_inner<'a>(
+///         _backend: &mut SliceWithPos<'a>,
+///     ) -> deser::Result<Self::DeserType<'a>> {
+///         Ok(Cell::new(""))
+///     }
+/// }
+/// ```
+pub unsafe trait DeserializeInner: Sized {
     /// The deserialization type associated with this type. It can be
     /// retrieved conveniently with the alias [`DeserType`].
     type DeserType<'a>;
@@ -242,13 +472,13 @@ impl<T: TypeHash + ReprHash + DeserializeInner> Deserialize for T {
     fn deserialize_full(backend: &mut impl ReadNoStd) -> Result<Self> {
         let mut backend = ReaderWithPos::new(backend);
         check_header::<Self>(&mut backend)?;
-        Self::_deserialize_full_inner(&mut backend)
+        deserialize_full_field(&mut backend, "ROOT")
     }
 
     fn deserialize_eps(backend: &'_ [u8]) -> Result<Self::DeserType<'_>> {
         let mut backend = SliceWithPos::new(backend);
         check_header::<Self>(&mut backend)?;
-        Self::_deserialize_eps_inner(&mut backend)
+        deserialize_eps_field::<Self>(&mut backend, "ROOT")
     }
 }
 
@@ -256,7 +486,10 @@ impl<T: TypeHash + ReprHash + DeserializeInner> Deserialize for T {
 ///
 /// Must be kept in sync with [`crate::ser::write_header`].
 pub fn check_header<T: Deserialize>(backend: &mut impl ReadWithPos) -> Result<()> {
+    #[cfg(feature = "alloc")]
     let self_type_name = core::any::type_name::<T>().to_string();
+    #[cfg(not(feature = "alloc"))]
+    let self_type_name = core::any::type_name::<T>();
 
     let mut type_hasher = xxhash_rust::xxh3::Xxh3::new();
     T::type_hash(&mut type_hasher);
@@ -290,9 +523,22 @@ pub fn check_header<T: Deserialize>(backend: &mut impl ReadWithPos) -> Result<()
         return Err(Error::UsizeSizeMismatch(usize_size));
     };
 
+    // The alignment of large slices has been recorded since minor version 2
+    if minor >= 2 {
+        let threshold = usize::_deserialize_full_inner(backend)?;
+        let align = usize::_deserialize_full_inner(backend)?;
+        if !align.is_power_of_two() {
+            return Err(Error::InvalidSliceAlignment(align));
+        }
+        backend.set_slice_alignment(SliceAlignment { threshold, align });
+    }
+
     let ser_type_hash = u64::_deserialize_full_inner(backend)?;
     let ser_repr_hash = u64::_deserialize_full_inner(backend)?;
+    #[cfg(feature = "alloc")]
     let ser_type_name = String::_deserialize_full_inner(backend)?;
+    #[cfg(not(feature = "alloc"))]
+    let ser_type_name = skip_type_name(backend)?;
 
     if ser_type_hash != self_type_hash {
         return Err(Error::WrongTypeHash {
@@ -314,6 +560,32 @@ pub fn check_header<T: Deserialize>(backend: &mut impl ReadWithPos) -> Result<()
     Ok(())
 }
 
+/// Skip the serialized type name in the header, returning a placeholder,
+/// as without allocation the name cannot be stored.
+#[cfg(not(feature = "alloc"))]
+fn skip_type_name(backend: &mut impl ReadWithPos) -> Result<TypeName> {
+    let mut len = usize::_deserialize_full_inner(backend)?;
+    backend.align_slice::<u8>(len)?;
+    let mut buf = [0; 64];
+    while len != 0 {
+        let chunk = len.min(buf.len());
+        backend.read_exact(&mut buf[..chunk])?;
+        len -= chunk;
+    }
+    Ok("<unknown>")
+}
+
+/// The type of the names of types in [errors](Error): a [`String`] if the
+/// `alloc` feature is enabled, and a static string otherwise, in which case
+/// the names of serialized types are reported as `<unknown>`.
+#[cfg(feature = "alloc")]
+pub type TypeName = String;
+/// The type of the names of types in [errors](Error): a [`String`] if the
+/// `alloc` feature is enabled, and a static string otherwise, in which case
+/// the names of serialized types are reported as `<unknown>`.
+#[cfg(not(feature = "alloc"))]
+pub type TypeName = &'static str;
+
 /// A helper trait that makes it possible to implement differently
 /// deserialization for [`crate::traits::ZeroCopy`] and [`crate::traits::DeepCopy`] types.
 /// See [`crate::traits::CopyType`] for more information.
@@ -330,13 +602,19 @@ pub trait DeserializeHelper<T: CopySelector> {
 
 #[derive(thiserror::Error, Debug)]
 /// Errors that can happen during deserialization.
+///
+/// This enum is non-exhaustive, as the available variants depend on the
+/// enabled features.
+#[non_exhaustive]
 pub enum Error {
-    #[error("Error reading stats for file during ε-serde deserialization: {0}")]
-    /// [`Deserialize::load_full`] could not open the provided file.
-    FileOpenError(std::io::Error),
-    #[error("Read error during ε-serde deserialization")]
-    /// The underlying reader returned an error.
-    ReadError,
+    #[error("Read error{} during ε-serde deserialization", AtPos(.pos))]
+    /// The underlying reader returned an error, or there is not enough data.
+    ReadError {
+        /// The position in the stream at which the read was attempted, if known.
+        pos: Option<usize>,
+        /// The error returned by the underlying reader, if any.
+        source: Option<crate::IoError>,
+    },
     /// The file is from ε-serde but the endianess is wrong.
     #[cfg_attr(
         target_endian = "big",
@@ -366,9 +644,21 @@ pub enum Error {
     #[error("Wrong magic cookie 0x{0:016x}. The byte stream does not come from ε-serde.")]
     /// The magic coookie is wrong. The byte sequence does not come from ε-serde.
     MagicCookieError(u64),
-    #[error("Invalid tag: 0x{0:02x}")]
+    #[error("Invalid tag: {0}")]
     /// A tag is wrong (e.g., for [`Option`]).
-    InvalidTag(usize),
+    InvalidTag(i128),
+    #[error("Unsupported version: {0}. The data was serialized by a newer version of the type.")]
+    /// The version of a structure with versioned fields is greater than the
+    /// version of the current definition of the structure.
+    UnsupportedVersion(u16),
+    #[error("Invalid position at index {0} of an indexed slice.")]
+    /// The position of an element of an [indexed slice](IndexedSlice) is out
+    /// of bounds, or the numbers of tags and positions differ.
+    InvalidIndexedSlice(usize),
+    #[error("Invalid slice alignment: {0}. The alignment must be a power of two.")]
+    /// The [alignment of large slices](SliceAlignment) recorded in the header
+    /// is not a power of two.
+    InvalidSliceAlignment(usize),
     #[error(
         r#"Wrong type hash. Expected: 0x{expected:016x} Actual: 0x{got:016x}.
 You are trying to deserialize a file with the wrong type.
@@ -377,8 +667,8 @@ The serialized type is '{expected_type_name}' and the deserialized type is '{got
     /// The type hash is wrong. Probably the user is trying to deserialize a
     /// file with the wrong type.
     WrongTypeHash {
-        got_type_name: String,
-        expected_type_name: String,
+        got_type_name: TypeName,
+        expected_type_name: TypeName,
         expected: u64,
         got: u64,
     },
@@ -392,9 +682,119 @@ The serialized type is '{expected_type_name}' and the deserialized type is '{got
     /// in-memory representations on the serialization arch and on the current one,
     /// usually because of alignment issues.
     WrongTypeReprHash {
-        got_type_name: String,
-        expected_type_name: String,
+        got_type_name: TypeName,
+        expected_type_name: TypeName,
         expected: u64,
         got: u64,
     },
+    #[cfg(feature = "alloc")]
+    #[error("{error} (in field {path} at offset {pos})")]
+    /// An error happened while deserializing a field. The path is built as
+    /// the error propagates, mirroring the field names of a
+    /// [`Schema`](crate::ser::Schema) (e.g., `ROOT.b.a.len`), and the offset is
+    /// the position in the stream at which the error was detected.
+    ///
+    /// This variant wraps all errors happening inside a field, and it is
+    /// available only with the `alloc` feature. Use [`Error::root`] to
+    /// retrieve the underlying error.
+    InField {
+        path: String,
+        pos: usize,
+        error: Box<Error>,
+    },
+}
+
+impl Error {
+    /// Create a [read error](Error::ReadError) with an unknown position.
+    pub fn read_error(source: Option<crate::IoError>) -> Self {
+        Self::ReadError { pos: None, source }
+    }
+
+    /// Set the position of a [read error](Error::ReadError), if it is not
+    /// known yet; other errors are returned unchanged.
+    pub fn at_pos(mut self, at: usize) -> Self {
+        if let Self::ReadError { pos, .. } = &mut self {
+            pos.get_or_insert(at);
+        }
+        self
+    }
+
+    /// Prepend a field name to the path of an error, wrapping the error in
+    /// an `InField` variant with offset `pos` if necessary.
+    ///
+    /// This method is used by [`deserialize_full_field`] and
+    /// [`deserialize_eps_field`]; without the `alloc` feature, it does
+    /// nothing.
+    pub fn in_field(self, _field_name: &str, _pos: usize) -> Self {
+        #[cfg(feature = "alloc")]
+        return match self {
+            Self::InField {
+                mut path,
+                pos,
+                error,
+            } => {
+                path.insert(0, '.');
+                path.insert_str(0, _field_name);
+                Self::InField { path, pos, error }
+            }
+            error => Self::InField {
+                path: _field_name.to_string(),
+                pos: _pos,
+                error: Box::new(error),
+            },
+        };
+        #[cfg(not(feature = "alloc"))]
+        self
+    }
+
+    /// Return the underlying error, removing the field context (the
+    /// `InField` variant available with the `alloc` feature), if any.
+    pub fn root(&self) -> &Self {
+        match self {
+            #[cfg(feature = "alloc")]
+            Self::InField { error, .. } => error.root(),
+            error => error,
+        }
+    }
+}
+
+/// Displays the position of a [read error](Error::ReadError), if known.
+struct AtPos<'a>(&'a Option<usize>);
+
+impl core::fmt::Display for AtPos<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self.0 {
+            Some(pos) => write!(f, " at position {}", pos),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Errors that can happen while loading or mapping a structure using the
+/// convenience methods of [`Deserialize`], such as [`Deserialize::load_full`],
+/// [`Deserialize::load_mem`], or [`Deserialize::mmap`].
+///
+/// Deserialization errors are wrapped in the [`LoadError::Deser`] variant, so,
+/// for example, a type mismatch can be detected by matching
+/// `LoadError::Deser(Error::WrongTypeHash { .. })`.
+#[cfg(feature = "std")]
+#[derive(thiserror::Error, Debug)]
+pub enum LoadError {
+    #[error(transparent)]
+    /// An I/O error occurred while opening or reading the file.
+    Io(#[from] std::io::Error),
+    #[cfg(feature = "mmap")]
+    #[error(transparent)]
+    /// Memory mapping or allocating the file failed.
+    Mmap(#[from] mmap_rs::Error),
+    #[error(transparent)]
+    /// The requested size or alignment of heap-allocated memory is invalid.
+    Layout(#[from] core::alloc::LayoutError),
+    #[error(transparent)]
+    /// The data could not be deserialized.
+    Deser(#[from] Error),
+    #[error("Empty range at offset {0}: there is no data to deserialize")]
+    /// The range passed to [`Deserialize::load_mem_range`] or
+    /// [`Deserialize::mmap_range`] is empty.
+    EmptyRange(u64),
 }
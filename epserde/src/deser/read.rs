@@ -32,7 +32,7 @@ use std::io::Read;
 impl<W: Read> ReadNoStd for W {
     #[inline(always)]
     fn read_exact(&mut self, buf: &mut [u8]) -> deser::Result<()> {
-        Read::read_exact(self, buf).map_err(|_| deser::Error::ReadError)
+        Read::read_exact(self, buf).map_err(|e| deser::Error::read_error(Some(e)))
     }
 }
 
@@ -46,4 +46,24 @@ pub trait ReadWithPos: ReadNoStd + Sized {
 
     /// Pad the cursor to the next multiple of [`MaxSizeOf::max_size_of`] 'T'.
     fn align<T: MaxSizeOf>(&mut self) -> deser::Result<()>;
+
+    /// Pad the cursor to the next multiple of the
+    /// [alignment](SliceAlignment::align_for) of a zero-copy slice of `len`
+    /// elements of type `T`.
+    ///
+    /// The default implementation delegates to [`ReadWithPos::align`], which
+    /// is correct only for [`SliceAlignment::NONE`]: implementations that need
+    /// to read data with a different slice alignment must override this
+    /// method and [`ReadWithPos::set_slice_alignment`].
+    fn align_slice<T: MaxSizeOf>(&mut self, _len: usize) -> deser::Result<()> {
+        self.align::<T>()
+    }
+
+    /// Set the [alignment of large zero-copy slices](SliceAlignment) used by
+    /// [`ReadWithPos::align_slice`].
+    ///
+    /// This method is called by [`check_header`](deser::check_header) with
+    /// the alignment recorded in the header. The default implementation
+    /// does nothing.
+    fn set_slice_alignment(&mut self, _slice_alignment: SliceAlignment) {}
 }
@@ -8,29 +8,50 @@
 use crate::prelude::*;
 
 use super::ReadNoStd;
+#[cfg(feature = "mem_dbg")]
 use mem_dbg::{MemDbg, MemSize};
 
 /// A wrapper for a [`ReadNoStd`] that implements [`ReadWithPos`]
 /// by keeping track of the current position.
-#[derive(Debug, MemDbg, MemSize)]
+#[derive(Debug)]
+#[cfg_attr(feature = "mem_dbg", derive(MemDbg, MemSize))]
 pub struct ReaderWithPos<'a, F: ReadNoStd> {
     /// What we actually readfrom
     backend: &'a mut F,
     /// How many bytes we have read from the start
     pos: usize,
+    /// The alignment of large zero-copy slices.
+    slice_alignment: SliceAlignment,
 }
 
 impl<'a, F: ReadNoStd> ReaderWithPos<'a, F> {
     #[inline(always)]
     /// Create a new [`ReadWithPos`] on top of a generic [`ReadNoStd`].
     pub fn new(backend: &'a mut F) -> Self {
-        Self { backend, pos: 0 }
+        Self {
+            backend,
+            pos: 0,
+            slice_alignment: SliceAlignment::NONE,
+        }
+    }
+
+    /// Read and discard `padding` bytes.
+    fn skip(&mut self, mut padding: usize) -> deser::Result<()> {
+        let mut buf = [0; 64];
+        while padding != 0 {
+            let len = padding.min(buf.len());
+            self.read_exact(&mut buf[..len])?;
+            padding -= len;
+        }
+        Ok(())
     }
 }
 
 impl<F: ReadNoStd> ReadNoStd for ReaderWithPos<'_, F> {
     fn read_exact(&mut self, buf: &mut [u8]) -> deser::Result<()> {
-        self.backend.read_exact(buf)?;
+        self.backend
+            .read_exact(buf)
+            .map_err(|e| e.at_pos(self.pos))?;
         self.pos += buf.len();
         Ok(())
     }
@@ -44,8 +65,17 @@ impl<F: ReadNoStd> ReadWithPos for ReaderWithPos<'_, F> {
     fn align<T: MaxSizeOf>(&mut self) -> deser::Result<()> {
         // Skip bytes as needed
         let padding = crate::pad_align_to(self.pos, T::max_size_of());
-        self.read_exact(&mut vec![0; padding])?;
+        self.skip(padding)?;
         // No alignment check, we are fully deserializing
         Ok(())
     }
+
+    fn align_slice<T: MaxSizeOf>(&mut self, len: usize) -> deser::Result<()> {
+        let padding = crate::pad_align_to(self.pos, self.slice_alignment.align_for::<T>(len));
+        self.skip(padding)
+    }
+
+    fn set_slice_alignment(&mut self, slice_alignment: SliceAlignment) {
+        self.slice_alignment = slice_alignment;
+    }
 }
@@ -7,14 +7,18 @@
 
 use super::*;
 use crate::prelude::*;
+#[cfg(feature = "mem_dbg")]
 use mem_dbg::{MemDbg, MemSize};
 
 /// [`std::io::Cursor`]-like trait for deserialization that does not
 /// depend on [`std`].
-#[derive(Debug, Clone, MemDbg, MemSize)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "mem_dbg", derive(MemDbg, MemSize))]
 pub struct SliceWithPos<'a> {
     pub data: &'a [u8],
     pub pos: usize,
+    /// The alignment of large zero-copy slices.
+    pub slice_alignment: SliceAlignment,
 }
 
 impl<'a> SliceWithPos<'a> {
@@ -22,6 +26,7 @@ impl<'a> SliceWithPos<'a> {
         Self {
             data: backend,
             pos: 0,
+            slice_alignment: SliceAlignment::NONE,
         }
     }
 
@@ -35,7 +40,7 @@ impl ReadNoStd for SliceWithPos<'_> {
     fn read_exact(&mut self, buf: &mut [u8]) -> deser::Result<()> {
         let len = buf.len();
         if len > self.data.len() {
-            return Err(Error::ReadError);
+            return Err(Error::read_error(None).at_pos(self.pos));
         }
         buf.copy_from_slice(&self.data[..len]);
         self.data = &self.data[len..];
@@ -58,10 +63,33 @@ impl ReadWithPos for SliceWithPos<'_> {
         let padding = crate::pad_align_to(self.pos, T::max_size_of());
         self.skip(padding);
         // Check that the ptr is indeed aligned
-        if self.data.as_ptr() as usize % T::max_size_of() != 0 {
+        if !(self.data.as_ptr() as usize).is_multiple_of(T::max_size_of()) {
             Err(Error::AlignmentError)
         } else {
             Ok(())
         }
     }
+
+    /// Pad the cursor to the alignment of a zero-copy slice.
+    ///
+    /// Note that this method checks that the absolute memory position is
+    /// aligned to [`MaxSizeOf::max_size_of`] `T`, but not to the
+    /// [alignment of large slices](SliceAlignment::align), which depends on
+    /// the alignment of the backing memory.
+    fn align_slice<T: MaxSizeOf>(&mut self, len: usize) -> deser::Result<()> {
+        let padding = crate::pad_align_to(self.pos, self.slice_alignment.align_for::<T>(len));
+        if padding > self.data.len() {
+            return Err(Error::read_error(None).at_pos(self.pos));
+        }
+        self.skip(padding);
+        if !(self.data.as_ptr() as usize).is_multiple_of(T::max_size_of()) {
+            Err(Error::AlignmentError)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn set_slice_alignment(&mut self, slice_alignment: SliceAlignment) {
+        self.slice_alignment = slice_alignment;
+    }
 }
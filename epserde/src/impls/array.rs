@@ -68,7 +68,8 @@ impl<T: DeepCopy + SerializeInner, const N: usize> SerializeHelper<Deep> for [T;
     }
 }
 
-impl<T: CopyType + DeserializeInner + 'static, const N: usize> DeserializeInner for [T; N]
+// SAFETY: both a reference and an array of covariant deserialization types are covariant
+unsafe impl<T: CopyType + DeserializeInner + 'static, const N: usize> DeserializeInner for [T; N]
 where
     [T; N]: DeserializeHelper<<T as CopyType>::Copy, FullType = [T; N]>,
 {
@@ -105,7 +106,7 @@ impl<T: ZeroCopy + DeserializeInner + 'static, const N: usize> DeserializeHelper
         backend: &mut SliceWithPos<'a>,
     ) -> deser::Result<<Self as DeserializeInner>::DeserType<'a>> {
         backend.align::<T>()?;
-        let bytes = std::mem::size_of::<[T; N]>();
+        let bytes = core::mem::size_of::<[T; N]>();
         let (pre, data, after) = unsafe { backend.data[..bytes].align_to::<[T; N]>() };
         debug_assert!(pre.is_empty());
         debug_assert!(after.is_empty());
@@ -123,7 +124,7 @@ impl<T: DeepCopy + DeserializeInner + 'static, const N: usize> DeserializeHelper
         let mut res = MaybeUninit::<[T; N]>::uninit();
         unsafe {
             for item in &mut res.assume_init_mut().iter_mut() {
-                std::ptr::write(item, T::_deserialize_full_inner(backend)?);
+                core::ptr::write(item, deserialize_full_field::<T>(backend, "item")?);
             }
             Ok(res.assume_init())
         }
@@ -135,7 +136,7 @@ impl<T: DeepCopy + DeserializeInner + 'static, const N: usize> DeserializeHelper
         let mut res = MaybeUninit::<<Self as DeserializeInner>::DeserType<'_>>::uninit();
         unsafe {
             for item in &mut res.assume_init_mut().iter_mut() {
-                std::ptr::write(item, T::_deserialize_eps_inner(backend)?);
+                core::ptr::write(item, deserialize_eps_field::<T>(backend, "item")?);
             }
             Ok(res.assume_init())
         }
@@ -16,6 +16,9 @@ use core::hash::Hash;
 use deser::*;
 use ser::*;
 
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::boxed::Box;
+
 impl<T> CopyType for Box<[T]> {
     type Copy = Deep;
 }
@@ -59,8 +62,16 @@ impl<T: DeepCopy + SerializeInner> SerializeHelper<Deep> for Box<[T]> {
     }
 }
 
+impl<T: IndexedEnum> SerializeHelper<Indexed> for Box<[T]> {
+    #[inline(always)]
+    fn _serialize_inner(&self, backend: &mut impl WriteWithNames) -> ser::Result<()> {
+        serialize_slice_indexed(backend, self)
+    }
+}
+
 // This delegates to a private helper trait which we can specialize on in stable rust
-impl<T: DeserializeInner + CopyType + 'static> DeserializeInner for Box<[T]>
+// SAFETY: references, boxed slices of covariant deserialization types, and indexed slices are covariant
+unsafe impl<T: DeserializeInner + CopyType + 'static> DeserializeInner for Box<[T]>
 where
     Box<[T]>: DeserializeHelper<<T as CopyType>::Copy, FullType = Box<[T]>>,
 {
@@ -109,3 +120,18 @@ impl<T: DeepCopy + DeserializeInner + 'static> DeserializeHelper<Deep> for Box<[
         Ok(deserialize_eps_vec_deep::<T>(backend)?.into_boxed_slice())
     }
 }
+
+impl<T: IndexedEnum> DeserializeHelper<Indexed> for Box<[T]> {
+    type FullType = Self;
+    type DeserType<'a> = IndexedSlice<'a, T>;
+    #[inline(always)]
+    fn _deserialize_full_inner_impl(backend: &mut impl ReadWithPos) -> deser::Result<Self> {
+        Ok(deserialize_full_vec_indexed(backend)?.into_boxed_slice())
+    }
+    #[inline(always)]
+    fn _deserialize_eps_inner_impl<'a>(
+        backend: &mut SliceWithPos<'a>,
+    ) -> deser::Result<<Self as DeserializeHelper<Indexed>>::DeserType<'a>> {
+        deserialize_eps_slice_indexed(backend)
+    }
+}
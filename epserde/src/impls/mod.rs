@@ -13,12 +13,13 @@ and [`DeserializeInner`](crate::deser::DeserializeInner) for standard Rust types
 */
 
 pub mod array;
+#[cfg(feature = "alloc")]
 pub mod boxed_slice;
 pub mod prim;
 pub mod slice;
-#[cfg(feature = "std")]
 pub mod stdlib;
+#[cfg(feature = "alloc")]
 pub mod string;
 pub mod tuple;
-#[cfg(any(feature = "alloc", feature = "std"))]
+#[cfg(feature = "alloc")]
 pub mod vec;
@@ -66,7 +66,8 @@ macro_rules! impl_prim_ser_des {
             }
         }
 
-		impl DeserializeInner for $ty {
+		// SAFETY: the deserialization type does not depend on the lifetime
+		unsafe impl DeserializeInner for $ty {
             #[inline(always)]
             fn _deserialize_full_inner(backend: &mut impl ReadWithPos) -> deser::Result<$ty> {
                 let mut buf = [0; size_of::<$ty>()];
@@ -108,7 +109,8 @@ macro_rules! impl_nonzero_ser_des {
             }
         }
 
-		impl DeserializeInner for $ty {
+		// SAFETY: the deserialization type does not depend on the lifetime
+		unsafe impl DeserializeInner for $ty {
             #[inline(always)]
             fn _deserialize_full_inner(backend: &mut impl ReadWithPos) -> deser::Result<$ty> {
                 let mut buf = [0; size_of::<$ty>()];
@@ -177,7 +179,8 @@ impl SerializeInner for bool {
     }
 }
 
-impl DeserializeInner for bool {
+// SAFETY: the deserialization type does not depend on the lifetime
+unsafe impl DeserializeInner for bool {
     #[inline(always)]
     fn _deserialize_full_inner(backend: &mut impl ReadWithPos) -> deser::Result<bool> {
         Ok(u8::_deserialize_full_inner(backend)? != 0)
@@ -205,7 +208,8 @@ impl SerializeInner for char {
     }
 }
 
-impl DeserializeInner for char {
+// SAFETY: the deserialization type does not depend on the lifetime
+unsafe impl DeserializeInner for char {
     #[inline(always)]
     fn _deserialize_full_inner(backend: &mut impl ReadWithPos) -> deser::Result<Self> {
         Ok(char::from_u32(u32::_deserialize_full_inner(backend)?).unwrap())
@@ -231,7 +235,8 @@ impl SerializeInner for () {
     }
 }
 
-impl DeserializeInner for () {
+// SAFETY: the deserialization type does not depend on the lifetime
+unsafe impl DeserializeInner for () {
     #[inline(always)]
     fn _deserialize_full_inner(_backend: &mut impl ReadWithPos) -> deser::Result<Self> {
         Ok(())
@@ -281,7 +286,8 @@ impl<T: ?Sized + TypeHash> SerializeInner for PhantomData<T> {
     }
 }
 
-impl<T: ?Sized + TypeHash> DeserializeInner for PhantomData<T> {
+// SAFETY: the deserialization type does not depend on the lifetime
+unsafe impl<T: ?Sized + TypeHash> DeserializeInner for PhantomData<T> {
     #[inline(always)]
     fn _deserialize_full_inner(_backend: &mut impl ReadWithPos) -> deser::Result<Self> {
         Ok(PhantomData::<T>)
@@ -332,14 +338,15 @@ impl<T: SerializeInner> SerializeInner for Option<T> {
     }
 }
 
-impl<T: DeserializeInner> DeserializeInner for Option<T> {
+// SAFETY: `Option` is covariant, and so is the deserialization type of `T`
+unsafe impl<T: DeserializeInner> DeserializeInner for Option<T> {
     #[inline(always)]
     fn _deserialize_full_inner(backend: &mut impl ReadWithPos) -> deser::Result<Self> {
-        let tag = u8::_deserialize_full_inner(backend)?;
+        let tag = deserialize_full_field::<u8>(backend, "Tag")?;
         match tag {
             0 => Ok(None),
-            1 => Ok(Some(T::_deserialize_full_inner(backend)?)),
-            _ => Err(deser::Error::InvalidTag(tag as usize)),
+            1 => Ok(Some(deserialize_full_field::<T>(backend, "Some")?)),
+            _ => Err(deser::Error::InvalidTag(tag.into())),
         }
     }
     type DeserType<'a> = Option<<T as DeserializeInner>::DeserType<'a>>;
@@ -347,11 +354,11 @@ impl<T: DeserializeInner> DeserializeInner for Option<T> {
     fn _deserialize_eps_inner<'a>(
         backend: &mut SliceWithPos<'a>,
     ) -> deser::Result<Self::DeserType<'a>> {
-        let tag = u8::_deserialize_full_inner(backend)?;
+        let tag = deserialize_full_field::<u8>(backend, "Tag")?;
         match tag {
             0 => Ok(None),
-            1 => Ok(Some(T::_deserialize_eps_inner(backend)?)),
-            _ => Err(deser::Error::InvalidTag(backend.data[0] as usize)),
+            1 => Ok(Some(deserialize_eps_field::<T>(backend, "Some")?)),
+            _ => Err(deser::Error::InvalidTag(tag.into())),
         }
     }
 }
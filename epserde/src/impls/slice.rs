@@ -37,8 +37,12 @@ assert_eq!(a, *b);
 */
 
 use crate::prelude::*;
+use core::hash::Hash;
+#[cfg(feature = "alloc")]
 use ser::*;
-use std::hash::Hash;
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::vec::Vec;
 
 impl<T: TypeHash> TypeHash for [T] {
     #[inline(always)]
@@ -53,6 +57,7 @@ impl<T> ReprHash for [T] {
     fn repr_hash(_hasher: &mut impl core::hash::Hasher, _offset_of: &mut usize) {}
 }
 
+#[cfg(feature = "alloc")]
 impl<T: SerializeInner + CopyType + TypeHash + ReprHash> Serialize for [T]
 where
     Vec<T>: SerializeHelper<<T as CopyType>::Copy>,
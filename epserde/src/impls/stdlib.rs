@@ -12,20 +12,25 @@ use core::{
     hash::Hash,
     ops::{Bound, RangeBounds},
 };
+use deser::helpers::{deserialize_eps_field, deserialize_full_field};
+#[cfg(feature = "std")]
 use std::collections::hash_map::DefaultHasher;
 
+#[cfg(feature = "std")]
 impl TypeHash for DefaultHasher {
     fn type_hash(hasher: &mut impl core::hash::Hasher) {
         "std::hash::DefaultHasher".hash(hasher);
     }
 }
 
+#[cfg(feature = "std")]
 impl ReprHash for DefaultHasher {
     fn repr_hash(hasher: &mut impl core::hash::Hasher, offset_of: &mut usize) {
         crate::traits::std_repr_hash::<Self>(hasher, offset_of)
     }
 }
 
+#[cfg(feature = "std")]
 impl MaxSizeOf for DefaultHasher {
     fn max_size_of() -> usize {
         core::mem::size_of::<Self>()
@@ -77,11 +82,12 @@ impl<Idx: SerializeInner> SerializeInner for core::ops::Range<Idx> {
     }
 }
 
-impl<Idx: DeserializeInner> DeserializeInner for core::ops::Range<Idx> {
+// SAFETY: ranges are covariant, and so is the deserialization type of `Idx`
+unsafe impl<Idx: DeserializeInner> DeserializeInner for core::ops::Range<Idx> {
     #[inline(always)]
     fn _deserialize_full_inner(backend: &mut impl ReadWithPos) -> deser::Result<Self> {
-        let start = Idx::_deserialize_full_inner(backend)?;
-        let end = Idx::_deserialize_full_inner(backend)?;
+        let start = deserialize_full_field::<Idx>(backend, "start")?;
+        let end = deserialize_full_field::<Idx>(backend, "end")?;
         Ok(core::ops::Range { start, end })
     }
     type DeserType<'a> = core::ops::Range<<Idx as DeserializeInner>::DeserType<'a>>;
@@ -89,8 +95,8 @@ impl<Idx: DeserializeInner> DeserializeInner for core::ops::Range<Idx> {
     fn _deserialize_eps_inner<'a>(
         backend: &mut SliceWithPos<'a>,
     ) -> deser::Result<Self::DeserType<'a>> {
-        let start = Idx::_deserialize_eps_inner(backend)?;
-        let end = Idx::_deserialize_eps_inner(backend)?;
+        let start = deserialize_eps_field::<Idx>(backend, "start")?;
+        let end = deserialize_eps_field::<Idx>(backend, "end")?;
         Ok(core::ops::Range { start, end })
     }
 }
@@ -106,10 +112,11 @@ impl<Idx: SerializeInner> SerializeInner for core::ops::RangeFrom<Idx> {
     }
 }
 
-impl<Idx: DeserializeInner> DeserializeInner for core::ops::RangeFrom<Idx> {
+// SAFETY: ranges are covariant, and so is the deserialization type of `Idx`
+unsafe impl<Idx: DeserializeInner> DeserializeInner for core::ops::RangeFrom<Idx> {
     #[inline(always)]
     fn _deserialize_full_inner(backend: &mut impl ReadWithPos) -> deser::Result<Self> {
-        let start = Idx::_deserialize_full_inner(backend)?;
+        let start = deserialize_full_field::<Idx>(backend, "start")?;
         Ok(core::ops::RangeFrom { start })
     }
     type DeserType<'a> = core::ops::RangeFrom<<Idx as DeserializeInner>::DeserType<'a>>;
@@ -117,7 +124,7 @@ impl<Idx: DeserializeInner> DeserializeInner for core::ops::RangeFrom<Idx> {
     fn _deserialize_eps_inner<'a>(
         backend: &mut SliceWithPos<'a>,
     ) -> deser::Result<Self::DeserType<'a>> {
-        let start = Idx::_deserialize_eps_inner(backend)?;
+        let start = deserialize_eps_field::<Idx>(backend, "start")?;
         Ok(core::ops::RangeFrom { start })
     }
 }
@@ -135,12 +142,13 @@ impl<Idx: SerializeInner> SerializeInner for core::ops::RangeInclusive<Idx> {
     }
 }
 
-impl<Idx: DeserializeInner> DeserializeInner for core::ops::RangeInclusive<Idx> {
+// SAFETY: ranges are covariant, and so is the deserialization type of `Idx`
+unsafe impl<Idx: DeserializeInner> DeserializeInner for core::ops::RangeInclusive<Idx> {
     #[inline(always)]
     fn _deserialize_full_inner(backend: &mut impl ReadWithPos) -> deser::Result<Self> {
-        let start = Idx::_deserialize_full_inner(backend)?;
-        let end = Idx::_deserialize_full_inner(backend)?;
-        let exhausted = bool::_deserialize_full_inner(backend)?;
+        let start = deserialize_full_field::<Idx>(backend, "start")?;
+        let end = deserialize_full_field::<Idx>(backend, "end")?;
+        let exhausted = deserialize_full_field::<bool>(backend, "exhausted")?;
         assert!(!exhausted, "cannot deserialize an exhausted range");
         Ok(start..=end)
     }
@@ -149,9 +157,9 @@ impl<Idx: DeserializeInner> DeserializeInner for core::ops::RangeInclusive<Idx>
     fn _deserialize_eps_inner<'a>(
         backend: &mut SliceWithPos<'a>,
     ) -> deser::Result<Self::DeserType<'a>> {
-        let start = Idx::_deserialize_eps_inner(backend)?;
-        let end = Idx::_deserialize_eps_inner(backend)?;
-        let exhausted = bool::_deserialize_full_inner(backend)?;
+        let start = deserialize_eps_field::<Idx>(backend, "start")?;
+        let end = deserialize_eps_field::<Idx>(backend, "end")?;
+        let exhausted = deserialize_full_field::<bool>(backend, "exhausted")?;
         assert!(!exhausted, "cannot deserialize an exhausted range");
         Ok(start..=end)
     }
@@ -168,10 +176,11 @@ impl<Idx: SerializeInner> SerializeInner for core::ops::RangeTo<Idx> {
     }
 }
 
-impl<Idx: DeserializeInner> DeserializeInner for core::ops::RangeTo<Idx> {
+// SAFETY: ranges are covariant, and so is the deserialization type of `Idx`
+unsafe impl<Idx: DeserializeInner> DeserializeInner for core::ops::RangeTo<Idx> {
     #[inline(always)]
     fn _deserialize_full_inner(backend: &mut impl ReadWithPos) -> deser::Result<Self> {
-        let end = Idx::_deserialize_full_inner(backend)?;
+        let end = deserialize_full_field::<Idx>(backend, "end")?;
         Ok(..end)
     }
     type DeserType<'a> = core::ops::RangeTo<<Idx as DeserializeInner>::DeserType<'a>>;
@@ -179,7 +188,7 @@ impl<Idx: DeserializeInner> DeserializeInner for core::ops::RangeTo<Idx> {
     fn _deserialize_eps_inner<'a>(
         backend: &mut SliceWithPos<'a>,
     ) -> deser::Result<Self::DeserType<'a>> {
-        let end = Idx::_deserialize_eps_inner(backend)?;
+        let end = deserialize_eps_field::<Idx>(backend, "end")?;
         Ok(..end)
     }
 }
@@ -195,10 +204,11 @@ impl<Idx: SerializeInner> SerializeInner for core::ops::RangeToInclusive<Idx> {
     }
 }
 
-impl<Idx: DeserializeInner> DeserializeInner for core::ops::RangeToInclusive<Idx> {
+// SAFETY: ranges are covariant, and so is the deserialization type of `Idx`
+unsafe impl<Idx: DeserializeInner> DeserializeInner for core::ops::RangeToInclusive<Idx> {
     #[inline(always)]
     fn _deserialize_full_inner(backend: &mut impl ReadWithPos) -> deser::Result<Self> {
-        let end = Idx::_deserialize_full_inner(backend)?;
+        let end = deserialize_full_field::<Idx>(backend, "end")?;
         Ok(..=end)
     }
     type DeserType<'a> = core::ops::RangeToInclusive<<Idx as DeserializeInner>::DeserType<'a>>;
@@ -206,7 +216,7 @@ impl<Idx: DeserializeInner> DeserializeInner for core::ops::RangeToInclusive<Idx
     fn _deserialize_eps_inner<'a>(
         backend: &mut SliceWithPos<'a>,
     ) -> deser::Result<Self::DeserType<'a>> {
-        let end = Idx::_deserialize_eps_inner(backend)?;
+        let end = deserialize_eps_field::<Idx>(backend, "end")?;
         Ok(..=end)
     }
 }
@@ -243,7 +253,8 @@ impl SerializeInner for core::ops::RangeFull {
     }
 }
 
-impl DeserializeInner for core::ops::RangeFull {
+// SAFETY: the deserialization type does not depend on the lifetime
+unsafe impl DeserializeInner for core::ops::RangeFull {
     #[inline(always)]
     fn _deserialize_full_inner(_backend: &mut impl ReadWithPos) -> deser::Result<Self> {
         Ok(core::ops::RangeFull)
@@ -301,19 +312,20 @@ impl<T: SerializeInner> SerializeInner for core::ops::Bound<T> {
     }
 }
 
-impl<T: DeserializeInner> DeserializeInner for core::ops::Bound<T> {
+// SAFETY: `Bound` is covariant, and so is the deserialization type of `T`
+unsafe impl<T: DeserializeInner> DeserializeInner for core::ops::Bound<T> {
     #[inline(always)]
     fn _deserialize_full_inner(backend: &mut impl ReadWithPos) -> deser::Result<Self> {
-        let tag = u8::_deserialize_full_inner(backend)?;
+        let tag = deserialize_full_field::<u8>(backend, "Tag")?;
         match tag {
             0 => Ok(core::ops::Bound::Unbounded),
-            1 => Ok(core::ops::Bound::Included(T::_deserialize_full_inner(
-                backend,
+            1 => Ok(core::ops::Bound::Included(deserialize_full_field::<T>(
+                backend, "Included",
             )?)),
-            2 => Ok(core::ops::Bound::Excluded(T::_deserialize_full_inner(
-                backend,
+            2 => Ok(core::ops::Bound::Excluded(deserialize_full_field::<T>(
+                backend, "Excluded",
             )?)),
-            _ => Err(deser::Error::InvalidTag(tag as usize)),
+            _ => Err(deser::Error::InvalidTag(tag.into())),
         }
     }
     type DeserType<'a> = core::ops::Bound<<T as DeserializeInner>::DeserType<'a>>;
@@ -321,16 +333,16 @@ impl<T: DeserializeInner> DeserializeInner for core::ops::Bound<T> {
     fn _deserialize_eps_inner<'a>(
         backend: &mut SliceWithPos<'a>,
     ) -> deser::Result<Self::DeserType<'a>> {
-        let tag = u8::_deserialize_full_inner(backend)?;
+        let tag = deserialize_full_field::<u8>(backend, "Tag")?;
         match tag {
             0 => Ok(core::ops::Bound::Unbounded),
-            1 => Ok(core::ops::Bound::Included(T::_deserialize_eps_inner(
-                backend,
+            1 => Ok(core::ops::Bound::Included(deserialize_eps_field::<T>(
+                backend, "Included",
             )?)),
-            2 => Ok(core::ops::Bound::Excluded(T::_deserialize_eps_inner(
-                backend,
+            2 => Ok(core::ops::Bound::Excluded(deserialize_eps_field::<T>(
+                backend, "Excluded",
             )?)),
-            _ => Err(deser::Error::InvalidTag(tag as usize)),
+            _ => Err(deser::Error::InvalidTag(tag.into())),
         }
     }
 }
@@ -382,18 +394,21 @@ impl<B: SerializeInner, C: SerializeInner> SerializeInner for core::ops::Control
     }
 }
 
-impl<B: DeserializeInner, C: DeserializeInner> DeserializeInner for core::ops::ControlFlow<B, C> {
+// SAFETY: `ControlFlow` is covariant, and so are the deserialization types of `B` and `C`
+unsafe impl<B: DeserializeInner, C: DeserializeInner> DeserializeInner
+    for core::ops::ControlFlow<B, C>
+{
     #[inline(always)]
     fn _deserialize_full_inner(backend: &mut impl ReadWithPos) -> deser::Result<Self> {
-        let tag = u8::_deserialize_full_inner(backend)?;
+        let tag = deserialize_full_field::<u8>(backend, "Tag")?;
         match tag {
-            1 => Ok(core::ops::ControlFlow::Break(B::_deserialize_full_inner(
-                backend,
+            1 => Ok(core::ops::ControlFlow::Break(deserialize_full_field::<B>(
+                backend, "Break",
             )?)),
             2 => Ok(core::ops::ControlFlow::Continue(
-                C::_deserialize_full_inner(backend)?,
+                deserialize_full_field::<C>(backend, "Continue")?,
             )),
-            _ => Err(deser::Error::InvalidTag(tag as usize)),
+            _ => Err(deser::Error::InvalidTag(tag.into())),
         }
     }
     type DeserType<'a> = core::ops::ControlFlow<
@@ -404,15 +419,15 @@ impl<B: DeserializeInner, C: DeserializeInner> DeserializeInner for core::ops::C
     fn _deserialize_eps_inner<'a>(
         backend: &mut SliceWithPos<'a>,
     ) -> deser::Result<Self::DeserType<'a>> {
-        let tag = u8::_deserialize_full_inner(backend)?;
+        let tag = deserialize_full_field::<u8>(backend, "Tag")?;
         match tag {
-            1 => Ok(core::ops::ControlFlow::Break(B::_deserialize_eps_inner(
-                backend,
+            1 => Ok(core::ops::ControlFlow::Break(deserialize_eps_field::<B>(
+                backend, "Break",
             )?)),
-            2 => Ok(core::ops::ControlFlow::Continue(C::_deserialize_eps_inner(
-                backend,
-            )?)),
-            _ => Err(deser::Error::InvalidTag(tag as usize)),
+            2 => Ok(core::ops::ControlFlow::Continue(
+                deserialize_eps_field::<C>(backend, "Continue")?,
+            )),
+            _ => Err(deser::Error::InvalidTag(tag.into())),
         }
     }
 }
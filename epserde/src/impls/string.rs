@@ -16,14 +16,13 @@ use core::hash::Hash;
 use deser::*;
 use ser::*;
 
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::{boxed::Box, string::String};
+
 impl CopyType for String {
     type Copy = Deep;
 }
 
-#[cfg(all(feature = "alloc", not(feature = "std")))]
-use alloc::string::String;
-
-#[cfg(feature = "alloc")]
 impl TypeHash for String {
     fn type_hash(hasher: &mut impl core::hash::Hasher) {
         "String".hash(hasher);
@@ -64,7 +63,8 @@ impl SerializeInner for String {
     }
 }
 
-impl DeserializeInner for String {
+// SAFETY: references are covariant in their lifetime
+unsafe impl DeserializeInner for String {
     fn _deserialize_full_inner(backend: &mut impl ReadWithPos) -> deser::Result<Self> {
         let slice = deserialize_full_vec_zero(backend)?;
         Ok(String::from_utf8(slice).unwrap())
@@ -96,7 +96,8 @@ impl SerializeInner for Box<str> {
     }
 }
 
-impl DeserializeInner for Box<str> {
+// SAFETY: references are covariant in their lifetime
+unsafe impl DeserializeInner for Box<str> {
     #[inline(always)]
     fn _deserialize_full_inner(backend: &mut impl ReadWithPos) -> deser::Result<Self> {
         Ok(String::_deserialize_full_inner(backend)?.into_boxed_str())
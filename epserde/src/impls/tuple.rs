@@ -58,7 +58,7 @@ macro_rules! impl_tuples {
             #[inline(always)]
             fn max_size_of() -> usize {
                 let mut max_size_of = 0;
-                $(if max_size_of < std::cmp::max(max_size_of, <$t>::max_size_of()) {
+                $(if max_size_of < core::cmp::max(max_size_of, <$t>::max_size_of()) {
                     max_size_of = <$t>::max_size_of();
                 })*
                 max_size_of
@@ -75,7 +75,8 @@ macro_rules! impl_tuples {
             }
         }
 
-		impl<$($t: ZeroCopy + TypeHash + ReprHash + 'static,)*> DeserializeInner for ($($t,)*) {
+		// SAFETY: references are covariant in their lifetime
+		unsafe impl<$($t: ZeroCopy + TypeHash + ReprHash + 'static,)*> DeserializeInner for ($($t,)*) {
             type DeserType<'a> = &'a ($($t,)*);
             fn _deserialize_full_inner(backend: &mut impl ReadWithPos) -> deser::Result<Self> {
                 deserialize_full_zero::<($($t,)*)>(backend)
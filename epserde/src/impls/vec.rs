@@ -65,8 +65,16 @@ impl<T: DeepCopy + SerializeInner> SerializeHelper<Deep> for Vec<T> {
     }
 }
 
+impl<T: IndexedEnum> SerializeHelper<Indexed> for Vec<T> {
+    #[inline(always)]
+    fn _serialize_inner(&self, backend: &mut impl WriteWithNames) -> ser::Result<()> {
+        serialize_slice_indexed(backend, self.as_slice())
+    }
+}
+
 // This delegates to a private helper trait which we can specialize on in stable rust
-impl<T: CopyType + DeserializeInner + 'static> DeserializeInner for Vec<T>
+// SAFETY: references, vectors of covariant deserialization types, and indexed slices are covariant
+unsafe impl<T: CopyType + DeserializeInner + 'static> DeserializeInner for Vec<T>
 where
     Vec<T>: DeserializeHelper<<T as CopyType>::Copy, FullType = Vec<T>>,
 {
@@ -113,3 +121,18 @@ impl<T: DeepCopy + DeserializeInner + 'static> DeserializeHelper<Deep> for Vec<T
         deserialize_eps_vec_deep::<T>(backend)
     }
 }
+
+impl<T: IndexedEnum> DeserializeHelper<Indexed> for Vec<T> {
+    type FullType = Self;
+    type DeserType<'a> = IndexedSlice<'a, T>;
+    #[inline(always)]
+    fn _deserialize_full_inner_impl(backend: &mut impl ReadWithPos) -> deser::Result<Self> {
+        deserialize_full_vec_indexed(backend)
+    }
+    #[inline(always)]
+    fn _deserialize_eps_inner_impl<'a>(
+        backend: &mut SliceWithPos<'a>,
+    ) -> deser::Result<<Self as DeserializeHelper<Indexed>>::DeserType<'a>> {
+        deserialize_eps_slice_indexed(backend)
+    }
+}
@@ -14,19 +14,36 @@ extern crate alloc;
 #[cfg(feature = "derive")]
 pub use epserde_derive::{Epserde, TypeInfo};
 
+#[cfg(feature = "std")]
+pub mod archive;
 pub mod deser;
 pub mod impls;
 pub mod ser;
 pub mod traits;
 pub mod utils;
 
+#[doc(hidden)]
+pub mod __private {
+    //! Items used by the code generated by the derive macros, which must
+    //! work in `no_std` crates.
+
+    #[cfg(all(feature = "alloc", not(feature = "std")))]
+    pub use alloc::vec::Vec;
+    #[cfg(feature = "std")]
+    pub use std::vec::Vec;
+}
+
 pub mod prelude {
     pub use crate::deser;
     pub use crate::deser::DeserType;
     pub use crate::deser::Deserialize;
     pub use crate::deser::DeserializeHelper;
     pub use crate::deser::DeserializeInner;
+    #[cfg(feature = "alloc")]
     pub use crate::deser::Flags;
+    #[cfg(feature = "std")]
+    pub use crate::deser::LoadError;
+    #[cfg(feature = "alloc")]
     pub use crate::deser::MemCase;
     pub use crate::deser::ReadWithPos;
     pub use crate::deser::SliceWithPos;
@@ -35,13 +52,15 @@ pub mod prelude {
     pub use crate::ser::SerializeHelper;
     pub use crate::ser::SerializeInner;
     pub use crate::traits::*;
+    #[cfg(feature = "alloc")]
     pub use crate::utils::*;
+    pub use crate::SliceAlignment;
     #[cfg(feature = "derive")]
     pub use epserde_derive::Epserde;
 }
 
 /// (Major, Minor) version of the file format, this follows semantic versioning
-pub const VERSION: (u16, u16) = (1, 1);
+pub const VERSION: (u16, u16) = (1, 2);
 
 /// Magic cookie, also used as endianess marker.
 pub const MAGIC: u64 = u64::from_ne_bytes(*b"epserde ");
@@ -54,6 +73,95 @@ pub fn pad_align_to(value: usize, align_to: usize) -> usize {
     value.wrapping_neg() & (align_to - 1)
 }
 
+/// The type of the errors of the underlying readers and writers carried by
+/// [`deser::Error::ReadError`] and [`ser::Error::WriteError`]:
+/// [`std::io::Error`] if the `std` feature is enabled, and
+/// [`Infallible`](core::convert::Infallible) otherwise, in which case no
+/// source error is available.
+#[cfg(feature = "std")]
+pub type IoError = std::io::Error;
+/// The type of the errors of the underlying readers and writers carried by
+/// [`deser::Error::ReadError`] and [`ser::Error::WriteError`]:
+/// [`std::io::Error`] if the `std` feature is enabled, and
+/// [`Infallible`](core::convert::Infallible) otherwise, in which case no
+/// source error is available.
+#[cfg(not(feature = "std"))]
+pub type IoError = core::convert::Infallible;
+
+/// Alignment of large zero-copy slices.
+///
+/// By default, a zero-copy slice is aligned to the
+/// [maximum size](traits::MaxSizeOf::max_size_of) of its elements. Using
+/// [`WriterWithPos::with_slice_alignment`](ser::WriterWithPos::with_slice_alignment),
+/// zero-copy slices whose size in bytes is at least
+/// [`threshold`](SliceAlignment::threshold) can be aligned to a larger boundary
+/// [`align`](SliceAlignment::align), such as 4 KiB or 2 MiB: in this way, when
+/// the serialized data is memory mapped large slices start at a page boundary,
+/// and can be independently mapped, advised (e.g., with `madvise`), or prefetched.
+///
+/// The alignment is recorded in the header, so deserialization does not need
+/// any configuration. Note that alignment is relative to the start of the
+/// serialized data: memory-mapped files are page aligned, but memory regions
+/// with a smaller alignment (e.g., those allocated by
+/// [`Deserialize::load_mem`](deser::Deserialize::load_mem)) will not align
+/// large slices to a page boundary.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "mem_dbg", derive(mem_dbg::MemDbg, mem_dbg::MemSize))]
+pub struct SliceAlignment {
+    /// The minimum size in bytes of the slices that are aligned to [`align`](SliceAlignment::align).
+    pub threshold: usize,
+    /// The alignment of large slices; it must be a power of two.
+    pub align: usize,
+}
+
+impl SliceAlignment {
+    /// No additional alignment.
+    pub const NONE: Self = Self {
+        threshold: usize::MAX,
+        align: 1,
+    };
+
+    /// Align slices of at least 64 KiB to 4 KiB pages.
+    pub const PAGE: Self = Self {
+        threshold: 1 << 16,
+        align: 1 << 12,
+    };
+
+    /// Align slices of at least 2 MiB to 2 MiB (huge) pages.
+    pub const HUGE_PAGE: Self = Self {
+        threshold: 1 << 21,
+        align: 1 << 21,
+    };
+
+    /// Create a new slice alignment.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if `align` is not a power of two.
+    pub const fn new(threshold: usize, align: usize) -> Self {
+        assert!(
+            align.is_power_of_two(),
+            "The alignment must be a power of two"
+        );
+        Self { threshold, align }
+    }
+
+    /// Return the alignment of a zero-copy slice of `len` elements of type `T`.
+    pub fn align_for<T: traits::MaxSizeOf>(&self, len: usize) -> usize {
+        if len.saturating_mul(core::mem::size_of::<T>()) >= self.threshold {
+            self.align.max(T::max_size_of())
+        } else {
+            T::max_size_of()
+        }
+    }
+}
+
+impl Default for SliceAlignment {
+    fn default() -> Self {
+        Self::NONE
+    }
+}
+
 #[test]
 
 fn test_pad_align_to() {
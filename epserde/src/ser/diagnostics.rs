@@ -0,0 +1,165 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Inria
+ * SPDX-FileCopyrightText: 2025 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+/*!
+
+Diagnostics emitted during serialization.
+
+Warnings about serialized data are never printed directly: they are
+reported as a [`Diagnostic`] to the [`DiagnosticSink`] configured on the
+writer using [`WriterWithPos::with_diagnostics`].
+
+If no sink is configured, diagnostics are routed to `tracing` if the
+feature `tracing` is enabled, to `log` if the feature `log` is enabled,
+and are discarded otherwise.
+
+*/
+
+use super::*;
+use core::fmt;
+#[cfg(feature = "mem_dbg")]
+use mem_dbg::{MemDbgImpl, MemSize, SizeFlags};
+
+/// The default threshold for [`Diagnostic::LargeDeepCopySlice`].
+pub const DEFAULT_LARGE_DEEP_COPY_SLICE_THRESHOLD: usize = 1 << 20;
+
+/// A diagnostic emitted during serialization.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Diagnostic {
+    /// A slice of deep-copy elements is longer than
+    /// [the threshold of the sink](DiagnosticSink::large_deep_copy_slice_threshold).
+    /// Such slices are serialized and deserialized element by element, so
+    /// they might be a performance issue.
+    LargeDeepCopySlice {
+        /// The name of the type of the elements.
+        type_name: &'static str,
+        /// The length of the slice.
+        len: usize,
+    },
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::LargeDeepCopySlice { type_name, len } => write!(
+                f,
+                "Serializing a slice of {} elements of deep-copy type {}; consider making the type zero-copy",
+                len, type_name
+            ),
+        }
+    }
+}
+
+/// A sink receiving [diagnostics](Diagnostic) emitted during serialization.
+///
+/// This trait is implemented by all closures taking a reference to a
+/// [`Diagnostic`].
+pub trait DiagnosticSink {
+    /// Report a diagnostic.
+    fn report(&self, diagnostic: &Diagnostic);
+
+    /// Return the length above which a slice of deep-copy elements causes a
+    /// [`Diagnostic::LargeDeepCopySlice`].
+    ///
+    /// The default implementation returns
+    /// [`DEFAULT_LARGE_DEEP_COPY_SLICE_THRESHOLD`].
+    fn large_deep_copy_slice_threshold(&self) -> usize {
+        DEFAULT_LARGE_DEEP_COPY_SLICE_THRESHOLD
+    }
+}
+
+impl<F: Fn(&Diagnostic)> DiagnosticSink for F {
+    fn report(&self, diagnostic: &Diagnostic) {
+        self(diagnostic)
+    }
+}
+
+/// A [`DiagnosticSink`] routing diagnostics to [`log::warn!`].
+#[cfg(feature = "log")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LogSink;
+
+#[cfg(feature = "log")]
+impl DiagnosticSink for LogSink {
+    fn report(&self, diagnostic: &Diagnostic) {
+        log::warn!("{}", diagnostic);
+    }
+}
+
+/// A [`DiagnosticSink`] routing diagnostics to [`tracing::warn!`].
+#[cfg(feature = "tracing")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TracingSink;
+
+#[cfg(feature = "tracing")]
+impl DiagnosticSink for TracingSink {
+    fn report(&self, diagnostic: &Diagnostic) {
+        tracing::warn!("{}", diagnostic);
+    }
+}
+
+/// Return the sink used when no sink has been configured on the writer.
+///
+/// It is a `TracingSink` if the feature `tracing` is enabled, a
+/// `LogSink` if the feature `log` is enabled, and `None` otherwise.
+pub fn default_sink() -> Option<&'static dyn DiagnosticSink> {
+    #[cfg(feature = "tracing")]
+    return Some(&TracingSink);
+    #[cfg(all(feature = "log", not(feature = "tracing")))]
+    return Some(&LogSink);
+    #[cfg(not(any(feature = "log", feature = "tracing")))]
+    return None;
+}
+
+/// An optional reference to a [`DiagnosticSink`], used to store
+/// the sink in a writer.
+#[derive(Clone, Copy, Default)]
+pub struct DiagnosticSinkRef<'a>(pub Option<&'a dyn DiagnosticSink>);
+
+impl<'a> DiagnosticSinkRef<'a> {
+    /// Return the sink, or the [default sink](default_sink) if no sink has
+    /// been configured.
+    pub fn get(&self) -> Option<&'a dyn DiagnosticSink> {
+        match self.0 {
+            Some(sink) => Some(sink),
+            None => default_sink(),
+        }
+    }
+}
+
+impl fmt::Debug for DiagnosticSinkRef<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("DiagnosticSinkRef")
+            .field(&self.0.map(|_| "dyn DiagnosticSink"))
+            .finish()
+    }
+}
+
+#[cfg(feature = "mem_dbg")]
+impl MemSize for DiagnosticSinkRef<'_> {
+    fn mem_size(&self, _flags: SizeFlags) -> usize {
+        core::mem::size_of::<Self>()
+    }
+}
+
+#[cfg(feature = "mem_dbg")]
+impl MemDbgImpl for DiagnosticSinkRef<'_> {}
+
+/// Report to the sink of `backend` that a slice of deep-copy elements
+/// of given length is being serialized, if the length is above
+/// [the threshold of the sink](DiagnosticSink::large_deep_copy_slice_threshold).
+pub fn check_deep_copy_slice_len<V>(backend: &impl WriteWithPos, len: usize) {
+    if let Some(sink) = backend.diagnostics() {
+        if len > sink.large_deep_copy_slice_threshold() {
+            sink.report(&Diagnostic::LargeDeepCopySlice {
+                type_name: core::any::type_name::<V>(),
+                len,
+            });
+        }
+    }
+}
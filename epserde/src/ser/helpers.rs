@@ -11,29 +11,28 @@ Helpers for serialization.
 
 */
 
-use super::{SerializeInner, WriteWithNames};
+use super::{check_deep_copy_slice_len, SerializeInner, WriteWithNames};
 use crate::ser;
 use crate::traits::*;
 
-pub fn check_zero_copy<V: SerializeInner>() {
-    if !V::IS_ZERO_COPY {
-        panic!(
-            "Cannot serialize type {} declared as zero-copy as it is not zero-copy",
-            core::any::type_name::<V>()
-        );
-    }
-}
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::vec;
 
 /// Serialize a zero-copy structure by writing its bytes properly [aligned](WriteWithNames::align).
 ///
 /// Note that this method uses a single `write_all` call to write the entire structure.
 ///
-/// Here we check [that the type is actually zero-copy](SerializeInner::IS_ZERO_COPY).
+/// Here we check at compile time [that the type is actually zero-copy](SerializeInner::IS_ZERO_COPY).
 pub fn serialize_zero<V: ZeroCopy + SerializeInner>(
     backend: &mut impl WriteWithNames,
     value: &V,
 ) -> ser::Result<()> {
-    check_zero_copy::<V>();
+    const {
+        assert!(
+            V::IS_ZERO_COPY,
+            "Cannot serialize a type declared as zero copy that is not zero copy"
+        )
+    };
     let buffer = unsafe {
         #[allow(clippy::manual_slice_size_calculation)]
         core::slice::from_raw_parts(value as *const V as *const u8, core::mem::size_of::<V>())
@@ -43,17 +42,22 @@ pub fn serialize_zero<V: ZeroCopy + SerializeInner>(
 }
 
 /// Serialize a slice of zero-copy structures by encoding
-/// its length first, and then its bytes properly [aligned](WriteWithNames::align).
+/// its length first, and then its bytes properly [aligned](WriteWithNames::align_slice).
 ///
 /// Note that this method uses a single `write_all`
 /// call to write the entire slice.
 ///
-/// Here we check [that the type is actually zero-copy](SerializeInner::IS_ZERO_COPY).
+/// Here we check at compile time [that the type is actually zero-copy](SerializeInner::IS_ZERO_COPY).
 pub fn serialize_slice_zero<V: SerializeInner + ZeroCopy>(
     backend: &mut impl WriteWithNames,
     data: &[V],
 ) -> ser::Result<()> {
-    check_zero_copy::<V>();
+    const {
+        assert!(
+            V::IS_ZERO_COPY,
+            "Cannot serialize a type declared as zero copy that is not zero copy"
+        )
+    };
 
     let len = data.len();
     backend.write("len", &len)?;
@@ -61,26 +65,84 @@ pub fn serialize_slice_zero<V: SerializeInner + ZeroCopy>(
         #[allow(clippy::manual_slice_size_calculation)]
         core::slice::from_raw_parts(data.as_ptr() as *const u8, len * core::mem::size_of::<V>())
     };
-    backend.align::<V>()?;
+    backend.align_slice::<V>(len)?;
     backend.write_bytes::<V>(buffer)
 }
 
-pub fn check_mismatch<V: SerializeInner>() {
-    if V::ZERO_COPY_MISMATCH {
-        eprintln!("Type {} is zero-copy, but it has not declared as such; use the #[deep_copy] attribute to silence this warning", core::any::type_name::<V>());
+/// Serialize a sequence of zero-copy structures of given length, returned by
+/// an iterator, with the same layout of [`serialize_slice_zero`].
+///
+/// Contrarily to [`serialize_slice_zero`], this method writes items one at a time.
+///
+/// # Panics
+///
+/// This method will panic if the iterator does not return exactly `len` items.
+pub fn serialize_slice_zero_iter<V: SerializeInner + ZeroCopy>(
+    backend: &mut impl WriteWithNames,
+    len: usize,
+    iter: impl IntoIterator<Item = V>,
+) -> ser::Result<()> {
+    const {
+        assert!(
+            V::IS_ZERO_COPY,
+            "Cannot serialize a type declared as zero copy that is not zero copy"
+        )
+    };
+
+    backend.write("len", &len)?;
+    backend.align_slice::<V>(len)?;
+    let mut count = 0;
+    for item in iter {
+        let buffer = unsafe {
+            core::slice::from_raw_parts(&item as *const V as *const u8, core::mem::size_of::<V>())
+        };
+        backend.write_bytes::<V>(buffer)?;
+        count += 1;
     }
+    assert_eq!(count, len, "The iterator returned a wrong number of items");
+    Ok(())
+}
+
+/// Serialize a slice of [indexed enums](IndexedEnum) by writing a zero-copy slice
+/// of tags, a zero-copy slice of positions, and then the slices of payloads.
+#[cfg(feature = "alloc")]
+pub fn serialize_slice_indexed<V: IndexedEnum>(
+    backend: &mut impl WriteWithNames,
+    data: &[V],
+) -> ser::Result<()> {
+    serialize_slice_zero_iter(backend, data.len(), data.iter().map(|item| item._tag()))?;
+    let mut counts = vec![0; V::NUM_VARIANTS];
+    serialize_slice_zero_iter::<usize>(
+        backend,
+        data.len(),
+        data.iter().map(|item| {
+            let variant = item._variant();
+            counts[variant] += 1;
+            counts[variant] - 1
+        }),
+    )?;
+    V::_serialize_payloads(data, backend)
 }
 
 /// Serialize a slice of deep-copy structures by encoding
 /// its length first, and then the contents item by item.
 ///
-/// Here we warn [that the type might actually be zero-copy](SerializeInner::ZERO_COPY_MISMATCH).
+/// If the slice is long, a [diagnostic](super::Diagnostic::LargeDeepCopySlice)
+/// is reported.
+///
+/// Here we check at compile time [that the type is not actually zero-copy](SerializeInner::ZERO_COPY_MISMATCH).
 pub fn serialize_slice_deep<V: SerializeInner>(
     backend: &mut impl WriteWithNames,
     data: &[V],
 ) -> ser::Result<()> {
-    check_mismatch::<V>();
+    const {
+        assert!(
+            !V::ZERO_COPY_MISMATCH,
+            "Cannot serialize a type that is zero copy, but has not been declared as such; use the #[deep_copy] attribute"
+        )
+    };
     let len = data.len();
+    check_deep_copy_slice_len::<V>(backend, len);
     backend.write("len", &len)?;
     for item in data.iter() {
         backend.write("item", item)?;
@@ -23,14 +23,28 @@ use crate::traits::*;
 use crate::*;
 
 use core::hash::Hasher;
+#[cfg(feature = "std")]
 use std::{io::BufWriter, path::Path};
 
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::string::{String, ToString};
+
+pub mod diagnostics;
+pub use diagnostics::*;
 pub mod write_with_names;
 pub use write_with_names::*;
 pub mod helpers;
 pub use helpers::*;
 pub mod write;
 pub use write::*;
+#[cfg(all(feature = "std", any(unix, windows)))]
+pub mod write_parallel;
+#[cfg(all(feature = "std", any(unix, windows)))]
+pub use write_parallel::*;
+#[cfg(feature = "alloc")]
+pub mod streaming;
+#[cfg(feature = "alloc")]
+pub use streaming::*;
 
 pub type Result<T> = core::result::Result<T, Error>;
 
@@ -53,6 +67,7 @@ pub trait Serialize: TypeHash + ReprHash {
     ///
     /// This method is mainly useful for debugging and to check cross-language
     /// interoperability.
+    #[cfg(feature = "alloc")]
     fn serialize_with_schema(&self, backend: &mut impl WriteNoStd) -> Result<Schema> {
         let mut writer_with_pos = WriterWithPos::new(backend);
         let mut schema_writer = SchemaWriter::new(&mut writer_with_pos);
@@ -60,16 +75,216 @@ pub trait Serialize: TypeHash + ReprHash {
         Ok(schema_writer.schema)
     }
 
+    /// Return the number of bytes that [`Serialize::serialize`] would write,
+    /// without serializing the type.
+    ///
+    /// The size is computed using a [`SizeCounter`], which performs the same
+    /// alignment logic as the serialization, but discards the data. Errors
+    /// are not caused by the counter, but by implementations of
+    /// [`SerializeInner`] that cannot be serialized (e.g., a
+    /// [`StreamingVec`] that has already been consumed).
+    fn serialized_size(&self) -> Result<usize> {
+        let mut counter = SizeCounter::new();
+        self.serialize_on_field_write(&mut counter)?;
+        Ok(counter.pos())
+    }
+
     /// Serialize the type using the given [`WriteWithNames`].
     fn serialize_on_field_write(&self, backend: &mut impl WriteWithNames) -> Result<()>;
 
     /// Convenience method to serialize to a file.
+    ///
+    /// The file is truncated and written in place, so readers (including
+    /// other processes that have mapped the file in memory) might see a
+    /// partially written file; see [`Serialize::store_atomic`] for an
+    /// alternative.
+    #[cfg(feature = "std")]
     fn store(&self, path: impl AsRef<Path>) -> Result<()> {
         let file = std::fs::File::create(path).map_err(Error::FileOpenError)?;
         let mut buf_writer = BufWriter::new(file);
-        self.serialize(&mut buf_writer)?;
+        let mut writer = WriterWithPos::new(&mut buf_writer).with_patching();
+        self.serialize_on_field_write(&mut writer)
+    }
+
+    /// Convenience method to serialize atomically to a file.
+    ///
+    /// The data is written to a temporary file in the same directory, which
+    /// is synced to disk and then renamed to `path`. Thus, readers see either
+    /// the previous content of the file or the new one, and processes that
+    /// have mapped the previous file in memory keep accessing its content.
+    /// If `sync_dir` is true, the directory containing the file is synced,
+    /// too, making the rename durable (this is a no-op on non-Unix systems).
+    ///
+    /// In case of error, the temporary file is removed.
+    #[cfg(feature = "std")]
+    fn store_atomic(&self, path: impl AsRef<Path>, sync_dir: bool) -> Result<()> {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        // Disambiguates concurrent stores within the same process
+        static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+        let path = path.as_ref();
+        let file_name = path
+            .file_name()
+            .ok_or_else(|| Error::FileOpenError(std::io::ErrorKind::InvalidInput.into()))?;
+        let mut tmp_name = std::ffi::OsString::from(".");
+        tmp_name.push(file_name);
+        tmp_name.push(format!(
+            ".{}.{}.tmp",
+            std::process::id(),
+            COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        let tmp_path = path.with_file_name(tmp_name);
+
+        let file = std::fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&tmp_path)
+            .map_err(Error::FileOpenError)?;
+        let result = (|| {
+            let mut buf_writer = BufWriter::new(file);
+            let mut writer = WriterWithPos::new(&mut buf_writer).with_patching();
+            self.serialize_on_field_write(&mut writer)?;
+            let file = buf_writer
+                .into_inner()
+                .map_err(|e| Error::write_error(Some(e.into_error())))?;
+            file.sync_all().map_err(|e| Error::write_error(Some(e)))?;
+            std::fs::rename(&tmp_path, path).map_err(|e| Error::write_error(Some(e)))
+        })();
+        if result.is_err() {
+            let _ = std::fs::remove_file(&tmp_path);
+            return result;
+        }
+
+        #[cfg(unix)]
+        if sync_dir {
+            let dir = match path.parent() {
+                Some(parent) if !parent.as_os_str().is_empty() => parent,
+                _ => Path::new("."),
+            };
+            std::fs::File::open(dir)
+                .and_then(|dir| dir.sync_all())
+                .map_err(|e| Error::write_error(Some(e)))?;
+        }
+        #[cfg(not(unix))]
+        let _ = sync_dir;
+
         Ok(())
     }
+
+    /// Convenience method to serialize to a file writing large zero-copy
+    /// slices in parallel.
+    ///
+    /// The length of the serialization is computed first, so that the file
+    /// can be preallocated; then, the data is written using a
+    /// [`ParallelFileWriter`], which writes zero-copy slices larger than
+    /// [`PARALLEL_THRESHOLD`] bytes splitting them in chunks written
+    /// concurrently using positional writes. The content of the file is
+    /// identical to that written by [`Serialize::store`].
+    #[cfg(all(feature = "std", any(unix, windows)))]
+    fn store_parallel(&self, path: impl AsRef<Path>) -> Result<()> {
+        let len = self.serialized_size()?;
+        let file = std::fs::File::create(path).map_err(Error::FileOpenError)?;
+        file.set_len(len as u64)
+            .map_err(|e| Error::write_error(Some(e)))?;
+        let mut writer = ParallelFileWriter::new(&file);
+        self.serialize_on_field_write(&mut writer)?;
+        writer.flush()
+    }
+
+    /// Convenience method to serialize to a memory-mapped file, returning
+    /// the mapping in read-only form.
+    ///
+    /// The length of the serialization is computed first, so that the file
+    /// can be created with its final length and mapped in memory; then, the
+    /// data is written directly into the mapping, so zero-copy slices are
+    /// copied without intermediate buffers or system calls. The content of
+    /// the file is identical to that written by [`Serialize::store`].
+    ///
+    /// The returned mapping can be used immediately to ε-copy deserialize the
+    /// structure using [`MemCase::from_mmap`](crate::deser::MemCase::from_mmap).
+    ///
+    /// Requires the `mmap` feature.
+    #[cfg(feature = "mmap")]
+    fn store_mmap(&self, path: impl AsRef<Path>) -> Result<mmap_rs::Mmap> {
+        let len = self.serialized_size()?;
+        let file = std::fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(path)
+            .map_err(Error::FileOpenError)?;
+        file.set_len(len as u64)
+            .map_err(|e| Error::write_error(Some(e)))?;
+        let mut mmap = unsafe {
+            mmap_rs::MmapOptions::new(len)
+                .map_err(Error::MmapError)?
+                .with_flags(mmap_rs::MmapFlags::SHARED)
+                .with_file(&file, 0)
+                .map_mut()
+                .map_err(Error::MmapError)?
+        };
+        let mut backend: &mut [u8] = &mut mmap;
+        self.serialize(&mut backend)?;
+        mmap.make_read_only().map_err(|(_, e)| Error::MmapError(e))
+    }
+
+    /// Serialize the type on a Tokio [`AsyncWrite`](tokio::io::AsyncWrite),
+    /// flushing it at the end, and return the number of bytes written.
+    ///
+    /// The bytes written are identical to those written by
+    /// [`Serialize::serialize`]. However, since serialization is synchronous,
+    /// it is performed in a [blocking task](tokio::task::spawn_blocking),
+    /// which sends the data in chunks through a bounded channel to the
+    /// backend, so the additional memory needed does not depend on the size
+    /// of the serialized data. This method must be called within a Tokio
+    /// runtime.
+    ///
+    /// The value is shared with the blocking task using an
+    /// [`Arc`](std::sync::Arc), as blocking tasks must be `'static`: the
+    /// returned future might be dropped (or leaked) while the task is still
+    /// running, so the task cannot borrow the value. Serializing in place
+    /// with [`block_in_place`](https://docs.rs/tokio/latest/tokio/task/fn.block_in_place.html)
+    /// would avoid the [`Arc`](std::sync::Arc), but it would require a
+    /// multi-threaded runtime and block one of its workers for the whole
+    /// serialization.
+    ///
+    /// Requires the `async` feature.
+    #[cfg(feature = "async")]
+    fn serialize_async(
+        self: std::sync::Arc<Self>,
+        backend: &mut (impl tokio::io::AsyncWrite + Unpin + Send),
+    ) -> impl core::future::Future<Output = Result<usize>> + Send
+    where
+        Self: Send + Sync + 'static,
+    {
+        async move {
+            use crate::utils::sync_bridge::*;
+
+            let (sender, mut receiver) = tokio::sync::mpsc::channel(CHANNEL_CAPACITY);
+            // Serialization flushes the writer, sending the last chunk
+            let task = tokio::task::spawn_blocking(move || {
+                self.serialize(&mut ChannelWriter::new(sender))
+            });
+
+            let mut pos = 0;
+            while let Some(chunk) = receiver.recv().await {
+                // Returning drops the receiver, which stops the serialization
+                write_all(backend, &chunk)
+                    .await
+                    .map_err(|e| Error::write_error(Some(e)).at_pos(pos))?;
+                pos += chunk.len();
+            }
+
+            let len = join(task)
+                .await
+                .map_err(|e| Error::write_error(Some(e)))??;
+            flush(backend)
+                .await
+                .map_err(|e| Error::write_error(Some(e)).at_pos(len))?;
+            Ok(len)
+        }
+    }
 }
 
 /// Inner trait to implement serialization of a type. This trait exists
@@ -84,17 +299,37 @@ pub trait SerializeInner {
     /// Inner constant used by the derive macros to keep
     /// track recursively of whether the type
     /// satisfies the conditions for being zero-copy. It is checked
-    /// at runtime against the trait implemented by the type, and
+    /// at compile time against the trait implemented by the type, and
     /// if a [`ZeroCopy`] type has this constant set to `false`
-    /// serialization will panic.
+    /// compilation will fail:
+    /// ```compile_fail
+    /// use epserde::prelude::*;
+    ///
+    /// #[derive(Epserde, Clone)]
+    /// #[zero_copy]
+    /// #[repr(C)]
+    /// struct NotZeroCopy {
+    ///     // Vectors are not zero-copy
+    ///     data: Vec<usize>,
+    /// }
+    /// ```
     const IS_ZERO_COPY: bool;
 
     /// Inner constant used by the derive macros to keep
     /// track of whether all fields of a type are zero-copy
     /// but neither the attribute `#[zero_copy]` nor the attribute `#[deep_copy]`
-    /// was specified. It is checked at runtime, and if it is true
-    /// a warning will be issued, as the type could be zero-copy,
-    /// which would be more efficient.
+    /// was specified. It is checked at compile time, and if it is true
+    /// compilation will fail, as the type could be zero-copy,
+    /// which would be more efficient:
+    /// ```compile_fail
+    /// use epserde::prelude::*;
+    ///
+    /// // Fails as it could be zero-copy, and #[deep_copy] is missing
+    /// #[derive(Epserde)]
+    /// struct CouldBeZeroCopy {
+    ///     a: usize,
+    /// }
+    /// ```
     const ZERO_COPY_MISMATCH: bool;
 
     /// Serialize this structure using the given backend.
@@ -123,6 +358,9 @@ pub fn write_header<T: TypeHash + ReprHash>(backend: &mut impl WriteWithNames) -
     backend.write("VERSION_MAJOR", &VERSION.0)?;
     backend.write("VERSION_MINOR", &VERSION.1)?;
     backend.write("USIZE_SIZE", &(core::mem::size_of::<usize>() as u8))?;
+    let slice_alignment = backend.slice_alignment();
+    backend.write("SLICE_ALIGN_THRESHOLD", &slice_alignment.threshold)?;
+    backend.write("SLICE_ALIGN", &slice_alignment.align)?;
 
     let mut type_hasher = xxhash_rust::xxh3::Xxh3::new();
     T::type_hash(&mut type_hasher);
@@ -133,7 +371,11 @@ pub fn write_header<T: TypeHash + ReprHash>(backend: &mut impl WriteWithNames) -
 
     backend.write("TYPE_HASH", &type_hasher.finish())?;
     backend.write("REPR_HASH", &repr_hasher.finish())?;
-    backend.write("TYPE_NAME", &core::any::type_name::<T>().to_string())
+    #[cfg(feature = "alloc")]
+    return backend.write("TYPE_NAME", &core::any::type_name::<T>().to_string());
+    // Same layout as a String, without allocating
+    #[cfg(not(feature = "alloc"))]
+    return helpers::serialize_slice_zero(backend, core::any::type_name::<T>().as_bytes());
 }
 
 /// A helper trait that makes it possible to implement differently
@@ -145,19 +387,124 @@ pub trait SerializeHelper<T: CopySelector> {
 
 #[derive(Debug)]
 /// Errors that can happen during serialization.
+///
+/// This enum is non-exhaustive, as the available variants (and the fields of
+/// [`Error::WriteError`]) depend on the enabled features.
+#[non_exhaustive]
 pub enum Error {
     /// The underlying writer returned an error.
-    WriteError,
+    ///
+    /// Use [`Error::write_error`] to create a new instance.
+    #[non_exhaustive]
+    WriteError {
+        /// The position in the stream at which the write was attempted, if known.
+        pos: Option<usize>,
+        /// The error returned by the underlying writer, if any.
+        source: Option<IoError>,
+        /// The dot-separated path of the field being written (e.g.,
+        /// `items.0.name`), empty if unknown.
+        #[cfg(feature = "alloc")]
+        path: String,
+    },
     /// [`Serialize::store`] could not open the provided file.
+    #[cfg(feature = "std")]
     FileOpenError(std::io::Error),
+    /// [`Serialize::store_mmap`] could not map the provided file in memory.
+    #[cfg(feature = "mmap")]
+    MmapError(mmap_rs::Error),
+    /// The backend cannot [patch](WriteWithPos::patch) the data at the given
+    /// position, either because it does not support patching or because the
+    /// data has not been written yet.
+    PatchError(usize),
+    /// A [`StreamingVecWriter`] was created on a backend with the given
+    /// [slice-alignment](SliceAlignment) threshold, but streaming requires
+    /// [`SliceAlignment::NONE`], as the length of the stream is not known in
+    /// advance.
+    #[cfg(feature = "alloc")]
+    UnsupportedSliceAlignment(usize),
+    /// A [`StreamingVec`] has already been serialized, consuming its iterator.
+    #[cfg(feature = "alloc")]
+    AlreadyConsumed,
+    /// An [`ArchiveWriter`](crate::archive::ArchiveWriter) already contains
+    /// an entry with the given name.
+    #[cfg(feature = "std")]
+    DuplicateEntry(String),
+}
+
+impl Error {
+    /// Create a [write error](Error::WriteError) with an unknown position
+    /// and field path.
+    pub fn write_error(source: Option<IoError>) -> Self {
+        Self::WriteError {
+            pos: None,
+            source,
+            #[cfg(feature = "alloc")]
+            path: String::new(),
+        }
+    }
+
+    /// Set the position of a [write error](Error::WriteError), if it is not
+    /// known yet; other errors are returned unchanged.
+    pub fn at_pos(mut self, at: usize) -> Self {
+        if let Self::WriteError { pos, .. } = &mut self {
+            pos.get_or_insert(at);
+        }
+        self
+    }
+
+    /// Prepend a field name to the path of a [write error](Error::WriteError);
+    /// other errors are returned unchanged.
+    ///
+    /// This method is called by [`WriteWithNames::write`] when the
+    /// serialization of a field fails, so that the path is built while the
+    /// error propagates. Without the `alloc` feature, it does nothing.
+    #[cfg_attr(not(feature = "alloc"), allow(unused_mut))]
+    pub fn in_field(mut self, _field_name: &str) -> Self {
+        #[cfg(feature = "alloc")]
+        if let Self::WriteError { path, .. } = &mut self {
+            if !path.is_empty() {
+                path.insert(0, '.');
+            }
+            path.insert_str(0, _field_name);
+        }
+        self
+    }
 }
 
-impl std::error::Error for Error {}
+impl core::error::Error for Error {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            Self::WriteError {
+                source: Some(source),
+                ..
+            } => Some(source),
+            #[cfg(feature = "mmap")]
+            Self::MmapError(error) => Some(error),
+            _ => None,
+        }
+    }
+}
 
 impl core::fmt::Display for Error {
     fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
         match self {
-            Self::WriteError => write!(f, "Write error during ε-serde serialization"),
+            Self::WriteError {
+                pos,
+                #[cfg(feature = "alloc")]
+                path,
+                ..
+            } => {
+                write!(f, "Write error")?;
+                if let Some(pos) = pos {
+                    write!(f, " at position {}", pos)?;
+                }
+                #[cfg(feature = "alloc")]
+                if !path.is_empty() {
+                    write!(f, " in field {}", path)?;
+                }
+                write!(f, " during ε-serde serialization")
+            }
+            #[cfg(feature = "std")]
             Self::FileOpenError(error) => {
                 write!(
                     f,
@@ -165,6 +512,40 @@ impl core::fmt::Display for Error {
                     error
                 )
             }
+            Self::PatchError(pos) => {
+                write!(
+                    f,
+                    "Cannot patch data at position {} during ε-serde serialization",
+                    pos
+                )
+            }
+            #[cfg(feature = "alloc")]
+            Self::UnsupportedSliceAlignment(threshold) => {
+                write!(
+                    f,
+                    "Streaming vectors cannot be written with a slice-alignment threshold ({} bytes) during ε-serde serialization",
+                    threshold
+                )
+            }
+            #[cfg(feature = "alloc")]
+            Self::AlreadyConsumed => {
+                write!(
+                    f,
+                    "A StreamingVec cannot be serialized twice during ε-serde serialization"
+                )
+            }
+            #[cfg(feature = "mmap")]
+            Self::MmapError(error) => {
+                write!(
+                    f,
+                    "Error memory-mapping file during ε-serde serialization: {}",
+                    error
+                )
+            }
+            #[cfg(feature = "std")]
+            Self::DuplicateEntry(name) => {
+                write!(f, "The archive already contains an entry named {:?}", name)
+            }
         }
     }
 }
@@ -0,0 +1,279 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Inria
+ * SPDX-FileCopyrightText: 2025 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+/*!
+
+Streaming serialization of vectors of unknown length.
+
+A vector of zero-copy items is serialized as its length followed by its
+items, so usually the whole vector must be in memory. A
+[`StreamingVecWriter`] writes instead a placeholder length, accepts items
+incrementally, and at the end [patches](WriteWithPos::patch) the length,
+producing data that can be deserialized as a [`Vec`].
+
+A [`StreamingVec`] wraps an iterator so that the items it returns can be
+streamed as a field of a larger structure: it has the same type hash of the
+corresponding vector, so a generic structure serialized with a
+[`StreamingVec`] field can be deserialized with a [`Vec`] field.
+
+Patching requires a backend supporting it, such as a [`WriterWithPos`]
+[with patching enabled](WriterWithPos::with_patching), which is used by
+[`Serialize::store`]:
+```rust
+# use epserde::prelude::*;
+# use epserde::ser::{StreamingVec, StreamingVecWriter, WriterWithPos};
+# fn main() -> Result<(), Box<dyn std::error::Error>> {
+#[derive(Epserde, Debug, PartialEq)]
+struct Index<O> {
+    offsets: O,
+    name: String,
+}
+
+let mut cursor = <AlignedCursor<maligned::A16>>::new();
+let mut writer = WriterWithPos::new(&mut cursor).with_patching();
+
+// A top-level vector
+let mut vec_writer = <StreamingVecWriter<u64, _>>::with_header(&mut writer)?;
+vec_writer.extend((0..100).map(|x| x * x))?;
+vec_writer.push(&42)?;
+assert_eq!(vec_writer.finish()?, 101);
+
+cursor.set_position(0);
+let vec = <Vec<u64>>::deserialize_full(&mut cursor)?;
+assert_eq!(vec[100], 42);
+
+// A field of a structure
+let mut cursor = <AlignedCursor<maligned::A16>>::new();
+let index = Index {
+    offsets: StreamingVec::new((0..100_u64).map(|x| x * x)),
+    name: "squares".to_string(),
+};
+index.serialize_on_field_write(&mut WriterWithPos::new(&mut cursor).with_patching())?;
+
+let index = <Index<Vec<u64>>>::deserialize_eps(cursor.as_bytes())?;
+assert_eq!(index.offsets[10], 100);
+assert_eq!(index.name, "squares");
+# Ok(())
+# }
+```
+
+*/
+
+use super::*;
+use core::cell::Cell;
+use core::marker::PhantomData;
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::vec::Vec;
+
+/// A writer serializing incrementally a [`Vec`] of zero-copy items whose
+/// length is not known in advance.
+///
+/// The writer writes a placeholder length and the padding preceding the
+/// items; items can then be written using [`push`](StreamingVecWriter::push)
+/// and [`extend`](StreamingVecWriter::extend), and
+/// [`finish`](StreamingVecWriter::finish) patches the length using
+/// [`WriteWithPos::patch`]. The resulting bytes are identical to those of
+/// the serialization of a [`Vec`] containing the same items.
+///
+/// Since the length is not known when the padding is written, the
+/// [alignment of large slices](SliceAlignment) cannot be applied: thus,
+/// creating a writer on a backend whose slice alignment is not
+/// [`SliceAlignment::NONE`] returns an
+/// [error](Error::UnsupportedSliceAlignment).
+///
+/// **You must call [`finish`](StreamingVecWriter::finish)**: a writer dropped
+/// without calling it leaves a zero placeholder length in the data, which
+/// will be deserialized as an empty vector followed by garbage. In debug
+/// builds, dropping an unfinished writer panics, unless the writer has
+/// already returned an error or the thread is panicking.
+pub struct StreamingVecWriter<'a, T, W: WriteWithNames> {
+    /// The backend we write on.
+    backend: &'a mut W,
+    /// The position of the placeholder length.
+    len_pos: usize,
+    /// The position of the items.
+    items_pos: usize,
+    /// The number of items written so far.
+    len: usize,
+    /// Whether [`finish`](StreamingVecWriter::finish) has been called, or
+    /// an error has been returned.
+    done: bool,
+    _marker: PhantomData<T>,
+}
+
+impl<'a, T: ZeroCopy + SerializeInner + TypeHash + ReprHash, W: WriteWithNames>
+    StreamingVecWriter<'a, T, W>
+{
+    /// Create a new [`StreamingVecWriter`] writing a header for a
+    /// [`Vec<T>`], so that the result can be deserialized as a top-level
+    /// structure.
+    pub fn with_header(backend: &'a mut W) -> Result<Self> {
+        Self::check_slice_alignment(backend)?;
+        write_header::<Vec<T>>(backend)?;
+        Self::new(backend)
+    }
+}
+
+impl<'a, T: ZeroCopy + SerializeInner, W: WriteWithNames> StreamingVecWriter<'a, T, W> {
+    /// Create a new [`StreamingVecWriter`] writing at the current position
+    /// of `backend` (e.g., as a field of a structure).
+    pub fn new(backend: &'a mut W) -> Result<Self> {
+        const {
+            assert!(
+                T::IS_ZERO_COPY,
+                "Cannot serialize a type declared as zero copy that is not zero copy"
+            )
+        };
+        Self::check_slice_alignment(backend)?;
+
+        let len_pos = backend.pos();
+        backend.write("len", &0_usize)?;
+        // Without slice alignment, items are aligned as a slice of any length
+        backend.align::<T>()?;
+        let items_pos = backend.pos();
+        Ok(Self {
+            backend,
+            len_pos,
+            items_pos,
+            len: 0,
+            done: false,
+            _marker: PhantomData,
+        })
+    }
+
+    /// Check that the slice alignment of `backend` is [`SliceAlignment::NONE`].
+    fn check_slice_alignment(backend: &W) -> Result<()> {
+        let slice_alignment = backend.slice_alignment();
+        if slice_alignment.threshold != SliceAlignment::NONE.threshold {
+            return Err(Error::UnsupportedSliceAlignment(slice_alignment.threshold));
+        }
+        Ok(())
+    }
+
+    /// Write an item.
+    pub fn push(&mut self, item: &T) -> Result<()> {
+        let buffer = unsafe {
+            core::slice::from_raw_parts(item as *const T as *const u8, core::mem::size_of::<T>())
+        };
+        if let Err(e) = self.backend.write_all(buffer) {
+            self.done = true;
+            return Err(e);
+        }
+        self.len += 1;
+        Ok(())
+    }
+
+    /// Write all items returned by an iterator.
+    pub fn extend(&mut self, iter: impl IntoIterator<Item = T>) -> Result<()> {
+        for item in iter {
+            self.push(&item)?;
+        }
+        Ok(())
+    }
+
+    /// Return the number of items written so far.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Return whether no item has been written so far.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Patch the length and return it.
+    pub fn finish(mut self) -> Result<usize> {
+        self.done = true;
+        self.backend
+            .written_bytes::<T>(self.items_pos, self.backend.pos() - self.items_pos);
+        self.backend
+            .patch(self.len_pos, &self.len.to_ne_bytes())
+            .map_err(|e| e.in_field("len"))?;
+        Ok(self.len)
+    }
+}
+
+impl<T, W: WriteWithNames> Drop for StreamingVecWriter<'_, T, W> {
+    fn drop(&mut self) {
+        #[cfg(feature = "std")]
+        let panicking = std::thread::panicking();
+        #[cfg(not(feature = "std"))]
+        let panicking = false;
+        debug_assert!(
+            self.done || panicking,
+            "A StreamingVecWriter was dropped without calling finish(): the length of the vector has not been patched"
+        );
+    }
+}
+
+/// A serialize-only wrapper for an iterator returning zero-copy items that
+/// serializes the items using a [`StreamingVecWriter`].
+///
+/// The type hash of a [`StreamingVec`] is that of the [`Vec`] of its items,
+/// so it can be used in place of a vector in a field of a generic structure,
+/// which can then be deserialized using a vector.
+///
+/// Since the iterator is consumed by serialization, a [`StreamingVec`] can be
+/// serialized only once, and further serializations return an
+/// [error](Error::AlreadyConsumed): in particular, methods such as
+/// [`Serialize::serialized_size`], [`Serialize::store_parallel`] or
+/// [`Serialize::store_mmap`], which serialize twice, cannot be used. Moreover,
+/// the backend must support [patching](WriteWithPos::patch).
+pub struct StreamingVec<I> {
+    iter: Cell<Option<I>>,
+}
+
+impl<I: Iterator> StreamingVec<I> {
+    /// Create a new [`StreamingVec`] that will serialize the items
+    /// returned by `iter`.
+    pub fn new(iter: impl IntoIterator<IntoIter = I>) -> Self {
+        Self {
+            iter: Cell::new(Some(iter.into_iter())),
+        }
+    }
+}
+
+impl<I> CopyType for StreamingVec<I> {
+    type Copy = Deep;
+}
+
+impl<I: Iterator> TypeHash for StreamingVec<I>
+where
+    I::Item: TypeHash,
+{
+    #[inline(always)]
+    fn type_hash(hasher: &mut impl core::hash::Hasher) {
+        <Vec<I::Item>>::type_hash(hasher);
+    }
+}
+
+impl<I: Iterator> ReprHash for StreamingVec<I>
+where
+    I::Item: ReprHash,
+{
+    #[inline(always)]
+    fn repr_hash(hasher: &mut impl core::hash::Hasher, offset_of: &mut usize) {
+        <Vec<I::Item>>::repr_hash(hasher, offset_of);
+    }
+}
+
+impl<I: Iterator> SerializeInner for StreamingVec<I>
+where
+    I::Item: ZeroCopy + SerializeInner,
+{
+    const IS_ZERO_COPY: bool = false;
+    const ZERO_COPY_MISMATCH: bool = false;
+
+    fn _serialize_inner(&self, backend: &mut impl WriteWithNames) -> Result<()> {
+        let iter = self.iter.take().ok_or(Error::AlreadyConsumed)?;
+        let mut writer = StreamingVecWriter::new(backend)?;
+        writer.extend(iter)?;
+        writer.finish()?;
+        Ok(())
+    }
+}
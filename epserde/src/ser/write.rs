@@ -12,6 +12,8 @@ No-std support for writing while keeping track of the current position.
  */
 
 use crate::prelude::*;
+use crate::ser::diagnostics::*;
+#[cfg(feature = "mem_dbg")]
 use mem_dbg::{MemDbg, MemSize};
 
 /// [`std::io::Write`]-like trait for serialization that does not
@@ -37,11 +39,11 @@ use std::io::Write;
 impl<W: Write> WriteNoStd for W {
     #[inline(always)]
     fn write_all(&mut self, buf: &[u8]) -> ser::Result<()> {
-        Write::write_all(self, buf).map_err(|_| ser::Error::WriteError)
+        Write::write_all(self, buf).map_err(|e| ser::Error::write_error(Some(e)))
     }
     #[inline(always)]
     fn flush(&mut self) -> ser::Result<()> {
-        Write::flush(self).map_err(|_| ser::Error::WriteError)
+        Write::flush(self).map_err(|e| ser::Error::write_error(Some(e)))
     }
 }
 
@@ -51,37 +53,118 @@ impl<W: Write> WriteNoStd for W {
 /// [`std::io::Seek`] would be a requirement much stronger than needed.
 pub trait WriteWithPos: WriteNoStd {
     fn pos(&self) -> usize;
+
+    /// Return the sink to which [diagnostics](Diagnostic) must be reported, if any.
+    ///
+    /// The default implementation returns the [default sink](default_sink).
+    fn diagnostics(&self) -> Option<&dyn DiagnosticSink> {
+        default_sink()
+    }
+
+    /// Return the [alignment of large zero-copy slices](SliceAlignment).
+    ///
+    /// The default implementation returns [`SliceAlignment::NONE`].
+    fn slice_alignment(&self) -> SliceAlignment {
+        SliceAlignment::NONE
+    }
+
+    /// Overwrite with `bytes` data previously written at position `pos`,
+    /// leaving the current position unchanged.
+    ///
+    /// This method makes it possible to write placeholders that are fixed
+    /// later (e.g., the length of a [`StreamingVecWriter`](crate::ser::StreamingVecWriter)).
+    ///
+    /// The default implementation returns a [patch error](ser::Error::PatchError).
+    fn patch(&mut self, pos: usize, _bytes: &[u8]) -> ser::Result<()> {
+        Err(ser::Error::PatchError(pos))
+    }
 }
 
 /// A wrapper for a [`WriteNoStd`] that implements [`WriteWithPos`]
 /// by keeping track of the current position.
-#[derive(Debug, MemDbg, MemSize)]
+#[derive(Debug)]
+#[cfg_attr(feature = "mem_dbg", derive(MemDbg, MemSize))]
 pub struct WriterWithPos<'a, F: WriteNoStd> {
     /// What we actually write on.
     backend: &'a mut F,
     /// How many bytes we have written from the start.
     pos: usize,
+    /// Where diagnostics are reported.
+    diagnostics: DiagnosticSinkRef<'a>,
+    /// The alignment of large zero-copy slices.
+    slice_alignment: SliceAlignment,
+    /// Overwrites data that was written the given number of bytes before
+    /// the current position, if patching is enabled.
+    patch: Option<PatchFn<F>>,
 }
 
+/// A function overwriting data that was written by a [`WriterWithPos`]
+/// the given number of bytes before the current position.
+type PatchFn<F> = fn(&mut F, usize, &[u8]) -> ser::Result<()>;
+
 impl<'a, F: WriteNoStd> WriterWithPos<'a, F> {
     #[inline(always)]
     /// Create a new [`WriterWithPos`] on top of a generic [`WriteNoStd`] `F`.
     pub fn new(backend: &'a mut F) -> Self {
-        Self { backend, pos: 0 }
+        Self {
+            backend,
+            pos: 0,
+            diagnostics: DiagnosticSinkRef::default(),
+            slice_alignment: SliceAlignment::NONE,
+            patch: None,
+        }
+    }
+
+    /// Set the sink to which [diagnostics](Diagnostic) are reported.
+    pub fn with_diagnostics(mut self, sink: &'a dyn DiagnosticSink) -> Self {
+        self.diagnostics = DiagnosticSinkRef(Some(sink));
+        self
+    }
+
+    /// Set the [alignment of large zero-copy slices](SliceAlignment).
+    pub fn with_slice_alignment(mut self, slice_alignment: SliceAlignment) -> Self {
+        self.slice_alignment = slice_alignment;
+        self
+    }
+}
+
+#[cfg(feature = "std")]
+impl<F: WriteNoStd + std::io::Seek> WriterWithPos<'_, F> {
+    /// Enable [patching](WriteWithPos::patch) of previously written data
+    /// by seeking the backend.
+    ///
+    /// Note that when the backend is a [`std::io::BufWriter`], every patch
+    /// flushes its buffer.
+    pub fn with_patching(mut self) -> Self {
+        self.patch = Some(|backend, back, bytes| {
+            use std::io::SeekFrom;
+            let seek_error = |e| ser::Error::write_error(Some(e));
+            backend
+                .seek(SeekFrom::Current(-(back as i64)))
+                .map_err(seek_error)?;
+            backend.write_all(bytes)?;
+            backend
+                .seek(SeekFrom::Current((back - bytes.len()) as i64))
+                .map_err(seek_error)?;
+            Ok(())
+        });
+        self
     }
 }
 
 impl<F: WriteNoStd> WriteNoStd for WriterWithPos<'_, F> {
     #[inline(always)]
     fn write_all(&mut self, buf: &[u8]) -> ser::Result<()> {
-        self.backend.write_all(buf)?;
+        self.backend
+            .write_all(buf)
+            .map_err(|e| e.at_pos(self.pos))?;
         self.pos += buf.len();
         Ok(())
     }
 
     #[inline(always)]
     fn flush(&mut self) -> ser::Result<()> {
-        self.backend.flush()
+        self.backend.flush().map_err(|e| e.at_pos(self.pos))
     }
 }
 
@@ -90,4 +173,23 @@ impl<F: WriteNoStd> WriteWithPos for WriterWithPos<'_, F> {
     fn pos(&self) -> usize {
         self.pos
     }
+
+    #[inline(always)]
+    fn diagnostics(&self) -> Option<&dyn DiagnosticSink> {
+        self.diagnostics.get()
+    }
+
+    #[inline(always)]
+    fn slice_alignment(&self) -> SliceAlignment {
+        self.slice_alignment
+    }
+
+    fn patch(&mut self, pos: usize, bytes: &[u8]) -> ser::Result<()> {
+        match self.patch {
+            Some(patch) if pos + bytes.len() <= self.pos => {
+                patch(self.backend, self.pos - pos, bytes).map_err(|e| e.at_pos(pos))
+            }
+            _ => Err(ser::Error::PatchError(pos)),
+        }
+    }
 }
@@ -0,0 +1,221 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Inria
+ * SPDX-FileCopyrightText: 2025 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+/*!
+
+Parallel serialization to files.
+
+A [`ParallelFileWriter`] writes on a file using positional writes (i.e.,
+`pwrite()`), and writes large zero-copy slices by splitting them in chunks
+that are written concurrently by several threads. It is used by
+[`Serialize::store_parallel`].
+
+*/
+
+use super::*;
+use std::fs::File;
+
+/// The default minimum size in bytes of the zero-copy slices written in parallel.
+pub const PARALLEL_THRESHOLD: usize = 1 << 20;
+
+/// The size of the buffer accumulating the bytes of non-parallel writes.
+const BUFFER_SIZE: usize = 1 << 16;
+
+/// Write all bytes of `buf` at the given offset of `file`.
+fn write_all_at(file: &File, mut buf: &[u8], mut offset: u64) -> std::io::Result<()> {
+    #[cfg(unix)]
+    use std::os::unix::fs::FileExt;
+    #[cfg(windows)]
+    use std::os::windows::fs::FileExt;
+
+    while !buf.is_empty() {
+        #[cfg(unix)]
+        let written = file.write_at(buf, offset);
+        #[cfg(windows)]
+        let written = file.seek_write(buf, offset);
+        match written {
+            Ok(0) => return Err(std::io::ErrorKind::WriteZero.into()),
+            Ok(n) => {
+                buf = &buf[n..];
+                offset += n as u64;
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(())
+}
+
+/// A [`WriteWithNames`] writing on a file with positional writes, and
+/// writing concurrently large zero-copy slices.
+///
+/// The bytes written are identical to those written by a [`WriterWithPos`],
+/// but zero-copy slices whose size in bytes is at least the
+/// [threshold](ParallelFileWriter::with_threshold) are split into chunks
+/// written concurrently by [several threads](ParallelFileWriter::with_threads).
+/// The remaining data is buffered, so [`WriteNoStd::flush`] must be called at
+/// the end of the serialization.
+#[derive(Debug)]
+pub struct ParallelFileWriter<'a> {
+    /// The file we write on.
+    file: &'a File,
+    /// How many bytes we have written from the start.
+    pos: usize,
+    /// Bytes written after `buffer_pos` that have not been written to the file yet.
+    buffer: Vec<u8>,
+    /// The position of the first byte of `buffer`.
+    buffer_pos: usize,
+    /// The number of threads writing large slices.
+    threads: usize,
+    /// The minimum size in bytes of the slices written in parallel.
+    threshold: usize,
+    /// Where diagnostics are reported.
+    diagnostics: DiagnosticSinkRef<'a>,
+    /// The alignment of large zero-copy slices.
+    slice_alignment: SliceAlignment,
+}
+
+impl<'a> ParallelFileWriter<'a> {
+    /// Create a new [`ParallelFileWriter`] writing on `file` from its start,
+    /// using as many threads as the [available
+    /// parallelism](std::thread::available_parallelism).
+    pub fn new(file: &'a File) -> Self {
+        Self {
+            file,
+            pos: 0,
+            buffer: Vec::with_capacity(BUFFER_SIZE),
+            buffer_pos: 0,
+            threads: std::thread::available_parallelism().map_or(1, |n| n.get()),
+            threshold: PARALLEL_THRESHOLD,
+            diagnostics: DiagnosticSinkRef::default(),
+            slice_alignment: SliceAlignment::NONE,
+        }
+    }
+
+    /// Set the number of threads writing large slices.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if `threads` is zero.
+    pub fn with_threads(mut self, threads: usize) -> Self {
+        assert!(threads > 0, "The number of threads must be positive");
+        self.threads = threads;
+        self
+    }
+
+    /// Set the minimum size in bytes of the zero-copy slices written in
+    /// parallel (default: [`PARALLEL_THRESHOLD`]).
+    pub fn with_threshold(mut self, threshold: usize) -> Self {
+        self.threshold = threshold;
+        self
+    }
+
+    /// Set the sink to which [diagnostics](Diagnostic) are reported.
+    pub fn with_diagnostics(mut self, sink: &'a dyn DiagnosticSink) -> Self {
+        self.diagnostics = DiagnosticSinkRef(Some(sink));
+        self
+    }
+
+    /// Set the [alignment of large zero-copy slices](SliceAlignment).
+    pub fn with_slice_alignment(mut self, slice_alignment: SliceAlignment) -> Self {
+        self.slice_alignment = slice_alignment;
+        self
+    }
+
+    /// Write the content of the buffer to the file.
+    fn write_buffer(&mut self) -> Result<()> {
+        write_all_at(self.file, &self.buffer, self.buffer_pos as u64)
+            .map_err(|e| Error::write_error(Some(e)).at_pos(self.buffer_pos))?;
+        self.buffer.clear();
+        self.buffer_pos = self.pos;
+        Ok(())
+    }
+
+    /// Write `buf` at the current position splitting it in chunks written
+    /// concurrently.
+    fn write_parallel(&mut self, buf: &[u8]) -> Result<()> {
+        self.write_buffer()?;
+        // An empty slice has no chunks, but chunk sizes must be positive
+        let chunk_size = buf.len().div_ceil(self.threads).max(1);
+        let file = self.file;
+        let pos = self.pos;
+        std::thread::scope(|scope| {
+            let handles = buf
+                .chunks(chunk_size)
+                .enumerate()
+                .map(|(i, chunk)| {
+                    let offset = pos + i * chunk_size;
+                    scope.spawn(move || {
+                        write_all_at(file, chunk, offset as u64)
+                            .map_err(|e| Error::write_error(Some(e)).at_pos(offset))
+                    })
+                })
+                .collect::<Vec<_>>();
+            handles
+                .into_iter()
+                .try_for_each(|handle| handle.join().unwrap())
+        })?;
+        self.pos += buf.len();
+        self.buffer_pos = self.pos;
+        Ok(())
+    }
+}
+
+impl WriteNoStd for ParallelFileWriter<'_> {
+    fn write_all(&mut self, buf: &[u8]) -> Result<()> {
+        self.buffer.extend_from_slice(buf);
+        self.pos += buf.len();
+        if self.buffer.len() >= BUFFER_SIZE {
+            self.write_buffer()?;
+        }
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.write_buffer()
+    }
+}
+
+impl WriteWithPos for ParallelFileWriter<'_> {
+    fn pos(&self) -> usize {
+        self.pos
+    }
+
+    fn diagnostics(&self) -> Option<&dyn DiagnosticSink> {
+        self.diagnostics.get()
+    }
+
+    fn slice_alignment(&self) -> SliceAlignment {
+        self.slice_alignment
+    }
+
+    fn patch(&mut self, pos: usize, bytes: &[u8]) -> Result<()> {
+        if pos + bytes.len() > self.pos {
+            return Err(Error::PatchError(pos));
+        }
+        // Bytes before buffer_pos have already been written to the file
+        let split = self.buffer_pos.saturating_sub(pos).min(bytes.len());
+        let (file_part, buffer_part) = bytes.split_at(split);
+        write_all_at(self.file, file_part, pos as u64)
+            .map_err(|e| Error::write_error(Some(e)).at_pos(pos))?;
+        if !buffer_part.is_empty() {
+            let start = pos + split - self.buffer_pos;
+            self.buffer[start..start + buffer_part.len()].copy_from_slice(buffer_part);
+        }
+        Ok(())
+    }
+}
+
+impl WriteWithNames for ParallelFileWriter<'_> {
+    fn write_bytes<V: SerializeInner + ZeroCopy>(&mut self, value: &[u8]) -> Result<()> {
+        if value.len() >= self.threshold && self.threads > 1 {
+            self.write_parallel(value)
+        } else {
+            self.write_all(value)
+        }
+    }
+}
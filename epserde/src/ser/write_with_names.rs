@@ -16,6 +16,14 @@ events happening during a serialization.
 */
 
 use super::*;
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+#[cfg(feature = "mem_dbg")]
 use mem_dbg::{MemDbg, MemSize};
 
 /// Trait extending [`WriteWithPos`] with methods providing
@@ -32,9 +40,10 @@ use mem_dbg::{MemDbg, MemSize};
 /// All methods have a default
 /// implementation that must be replicated in other implementations.
 ///
-/// There are two implementations of [`WriteWithNames`]: [`WriterWithPos`],
-/// which uses the default implementation, and [`SchemaWriter`],
-/// which additionally records a [`Schema`] of the serialized data.
+/// The main implementations of [`WriteWithNames`] are [`WriterWithPos`],
+/// which uses the default implementation, [`SchemaWriter`],
+/// which additionally records a [`Schema`] of the serialized data, and
+/// [`SizeCounter`], which discards the data and just counts the bytes.
 pub trait WriteWithNames: WriteWithPos + Sized {
     /// Add some zero padding so that `self.pos() % V:max_size_of() == 0.`
     ///
@@ -47,13 +56,31 @@ pub trait WriteWithNames: WriteWithPos + Sized {
         Ok(())
     }
 
+    /// Add some zero padding so that `self.pos()` is a multiple of the
+    /// [alignment](SliceAlignment::align_for) of a zero-copy slice of `len`
+    /// elements of type `V` given by [`WriteWithPos::slice_alignment`].
+    ///
+    /// Other implementations must write the same number of zeros.
+    fn align_slice<V: MaxSizeOf>(&mut self, len: usize) -> Result<()> {
+        let align = self.slice_alignment().align_for::<V>(len);
+        let padding = pad_align_to(self.pos(), align);
+        for _ in 0..padding {
+            self.write_all(&[0])?;
+        }
+        Ok(())
+    }
+
     /// Write a value with an associated name.
     ///
-    /// The default implementation simply delegates to [`SerializeInner::_serialize_inner`].
+    /// The default implementation simply delegates to [`SerializeInner::_serialize_inner`],
+    /// adding the field name to the path of [write errors](Error::in_field).
     /// Other implementations might use the name information (e.g., [`SchemaWriter`]),
-    /// but they must in the end delegate to [`SerializeInner::_serialize_inner`].
-    fn write<V: SerializeInner>(&mut self, _field_name: &str, value: &V) -> Result<()> {
-        value._serialize_inner(self)
+    /// but they must in the end delegate to [`SerializeInner::_serialize_inner`]
+    /// in the same way.
+    fn write<V: SerializeInner>(&mut self, field_name: &str, value: &V) -> Result<()> {
+        value
+            ._serialize_inner(self)
+            .map_err(|e| e.in_field(field_name))
     }
 
     /// Write the memory representation of a (slice of a) zero-copy type.
@@ -64,13 +91,108 @@ pub trait WriteWithNames: WriteWithPos + Sized {
     fn write_bytes<V: SerializeInner + ZeroCopy>(&mut self, value: &[u8]) -> Result<()> {
         self.write_all(value)
     }
+
+    /// Record that `len` bytes of the memory representation of (a slice of)
+    /// a zero-copy type have been written at position `pos` using
+    /// [`WriteNoStd::write_all`], as it happens when the data is written
+    /// incrementally (e.g., by a [`StreamingVecWriter`]).
+    ///
+    /// The default implementation does nothing. Other implementations might
+    /// record the data in the same way of [`WriteWithNames::write_bytes`]
+    /// (e.g., [`SchemaWriter`]).
+    fn written_bytes<V: SerializeInner + ZeroCopy>(&mut self, _pos: usize, _len: usize) {}
 }
 
 impl<F: WriteNoStd> WriteWithNames for WriterWithPos<'_, F> {}
 
+/// A [`WriteWithNames`] that discards the data and just keeps track of the
+/// position, so that the length of a serialization can be computed without
+/// writing it (see [`Serialize::serialized_size`]).
+///
+/// No [diagnostics](Diagnostic) are reported, as they will be reported
+/// by the actual serialization.
+#[derive(Debug, Default)]
+#[cfg_attr(feature = "mem_dbg", derive(MemDbg, MemSize))]
+pub struct SizeCounter {
+    /// How many bytes we would have written from the start.
+    pos: usize,
+    /// The alignment of large zero-copy slices.
+    slice_alignment: SliceAlignment,
+}
+
+impl SizeCounter {
+    /// Create a new [`SizeCounter`].
+    pub fn new() -> Self {
+        Self {
+            pos: 0,
+            slice_alignment: SliceAlignment::NONE,
+        }
+    }
+
+    /// Set the [alignment of large zero-copy slices](SliceAlignment).
+    pub fn with_slice_alignment(mut self, slice_alignment: SliceAlignment) -> Self {
+        self.slice_alignment = slice_alignment;
+        self
+    }
+}
+
+impl WriteNoStd for SizeCounter {
+    #[inline(always)]
+    fn write_all(&mut self, buf: &[u8]) -> Result<()> {
+        self.pos += buf.len();
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn flush(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl WriteWithPos for SizeCounter {
+    #[inline(always)]
+    fn pos(&self) -> usize {
+        self.pos
+    }
+
+    #[inline(always)]
+    fn diagnostics(&self) -> Option<&dyn DiagnosticSink> {
+        None
+    }
+
+    #[inline(always)]
+    fn slice_alignment(&self) -> SliceAlignment {
+        self.slice_alignment
+    }
+
+    fn patch(&mut self, pos: usize, bytes: &[u8]) -> Result<()> {
+        if pos + bytes.len() <= self.pos {
+            Ok(())
+        } else {
+            Err(Error::PatchError(pos))
+        }
+    }
+}
+
+impl WriteWithNames for SizeCounter {
+    #[inline(always)]
+    fn align<V: MaxSizeOf>(&mut self) -> Result<()> {
+        self.pos += pad_align_to(self.pos, V::max_size_of());
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn align_slice<V: MaxSizeOf>(&mut self, len: usize) -> Result<()> {
+        self.pos += pad_align_to(self.pos, self.slice_alignment.align_for::<V>(len));
+        Ok(())
+    }
+}
+
+#[cfg(feature = "alloc")]
 /// Information about data written during serialization, either fields or
 /// ancillary data such as option tags and slice lengths.
-#[derive(Debug, Clone, MemDbg, MemSize)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "mem_dbg", derive(MemDbg, MemSize))]
 pub struct SchemaRow {
     /// Name of the piece of data.
     pub field: String,
@@ -85,11 +207,14 @@ pub struct SchemaRow {
     pub align: usize,
 }
 
-#[derive(Default, Debug, Clone, MemDbg, MemSize)]
+#[cfg(feature = "alloc")]
+#[derive(Default, Debug, Clone)]
+#[cfg_attr(feature = "mem_dbg", derive(MemDbg, MemSize))]
 /// A vector containing all the fields written during serialization, including
 /// ancillary data such as slice lengths and [`Option`] tags.
 pub struct Schema(pub Vec<SchemaRow>);
 
+#[cfg(feature = "alloc")]
 impl Schema {
     /// Return a CSV representation of the schema, including data.
     ///
@@ -148,9 +273,11 @@ impl Schema {
     }
 }
 
+#[cfg(feature = "alloc")]
 /// A [`WriteWithNames`] that keeps track of the data written on an underlying
 /// [`WriteWithPos`] in a [`Schema`].
-#[derive(Debug, MemDbg, MemSize)]
+#[derive(Debug)]
+#[cfg_attr(feature = "mem_dbg", derive(MemDbg, MemSize))]
 pub struct SchemaWriter<'a, W> {
     /// The schema so far.
     pub schema: Schema,
@@ -160,6 +287,7 @@ pub struct SchemaWriter<'a, W> {
     writer: &'a mut W,
 }
 
+#[cfg(feature = "alloc")]
 impl<'a, W: WriteWithPos> SchemaWriter<'a, W> {
     #[inline(always)]
     /// Create a new empty [`SchemaWriter`] on top of a generic writer `W`.
@@ -170,43 +298,70 @@ impl<'a, W: WriteWithPos> SchemaWriter<'a, W> {
             writer: backend,
         }
     }
+    /// Write `padding` zeros, recording them in the schema.
+    fn pad(&mut self, padding: usize) -> Result<()> {
+        if padding != 0 {
+            self.schema.0.push(SchemaRow {
+                field: "PADDING".into(),
+                ty: format!("[u8; {}]", padding),
+                offset: self.pos(),
+                size: padding,
+                align: 1,
+            });
+            for _ in 0..padding {
+                self.write_all(&[0])?;
+            }
+        }
+
+        Ok(())
+    }
 }
+
+#[cfg(feature = "alloc")]
 impl<W: WriteNoStd> WriteNoStd for SchemaWriter<'_, W> {
-    fn write_all(&mut self, buf: &[u8]) -> ser::Result<()> {
+    fn write_all(&mut self, buf: &[u8]) -> Result<()> {
         self.writer.write_all(buf)
     }
 
-    fn flush(&mut self) -> ser::Result<()> {
+    fn flush(&mut self) -> Result<()> {
         self.writer.flush()
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<W: WriteWithPos> WriteWithPos for SchemaWriter<'_, W> {
     fn pos(&self) -> usize {
         self.writer.pos()
     }
+
+    fn diagnostics(&self) -> Option<&dyn DiagnosticSink> {
+        self.writer.diagnostics()
+    }
+
+    fn slice_alignment(&self) -> SliceAlignment {
+        self.writer.slice_alignment()
+    }
+
+    fn patch(&mut self, pos: usize, bytes: &[u8]) -> Result<()> {
+        self.writer.patch(pos, bytes)
+    }
 }
 
 /// WARNING: these implementations must be kept in sync with the ones
 /// in the default implementation of [`WriteWithNames`].
+#[cfg(feature = "alloc")]
 impl<W: WriteWithPos> WriteWithNames for SchemaWriter<'_, W> {
     #[inline(always)]
     fn align<T: MaxSizeOf>(&mut self) -> Result<()> {
         let padding = pad_align_to(self.pos(), T::max_size_of());
-        if padding != 0 {
-            self.schema.0.push(SchemaRow {
-                field: "PADDING".into(),
-                ty: format!("[u8; {}]", padding),
-                offset: self.pos(),
-                size: padding,
-                align: 1,
-            });
-            for _ in 0..padding {
-                self.write_all(&[0])?;
-            }
-        }
+        self.pad(padding)
+    }
 
-        Ok(())
+    #[inline(always)]
+    fn align_slice<T: MaxSizeOf>(&mut self, len: usize) -> Result<()> {
+        let align = self.slice_alignment().align_for::<T>(len);
+        let padding = pad_align_to(self.pos(), align);
+        self.pad(padding)
     }
 
     #[inline(always)]
@@ -216,7 +371,9 @@ impl<W: WriteWithPos> WriteWithNames for SchemaWriter<'_, W> {
         let pos = self.pos();
 
         let len = self.schema.0.len();
-        value._serialize_inner(self)?;
+        value
+            ._serialize_inner(self)
+            .map_err(|e| e.in_field(field_name))?;
 
         // This is slightly inefficient because we have to shift
         // the whole vector, but it's not a big deal and it keeps
@@ -237,18 +394,22 @@ impl<W: WriteWithPos> WriteWithNames for SchemaWriter<'_, W> {
 
     #[inline(always)]
     fn write_bytes<V: SerializeInner + ZeroCopy>(&mut self, value: &[u8]) -> Result<()> {
-        self.path.push("zero".to_string());
         // Note that we are writing the schema row of the field before
         // having written its content.
+        self.written_bytes::<V>(self.pos(), value.len());
+        self.write_all(value)
+    }
+
+    #[inline(always)]
+    fn written_bytes<V: SerializeInner + ZeroCopy>(&mut self, pos: usize, len: usize) {
+        self.path.push("zero".to_string());
         self.schema.0.push(SchemaRow {
             field: self.path.join("."),
             ty: core::any::type_name::<V>().to_string(),
-            offset: self.pos(),
-            size: value.len(),
+            offset: pos,
+            size: len,
             align: V::max_size_of(),
         });
         self.path.pop();
-
-        self.write_all(value)
     }
 }
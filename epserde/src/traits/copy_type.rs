@@ -17,12 +17,13 @@ use sealed::sealed;
 /// Internal trait used to select whether a type is zero-copy
 /// or deep-copy.
 ///
-/// It has only two implementations, [`Zero`] and [`Deep`].
+/// It has three implementations, [`Zero`], [`Deep`], and [`Indexed`].
 ///
 /// In the first case, the type can be serialized
 /// from memory and deserialized to memory as a sequence of bytes;
 /// in the second case, one has to deserialize the type field
-/// by field.
+/// by field. The third case is a deep-copy enum whose slices
+/// are serialized using an [indexed layout](crate::traits::IndexedEnum).
 #[sealed]
 pub trait CopySelector {
     const IS_ZERO_COPY: bool;
@@ -43,6 +44,15 @@ impl CopySelector for Deep {
     const IS_ZERO_COPY: bool = false;
 }
 
+/// An implementation of a [`CopySelector`] specifying that a type is an
+/// [indexed enum](crate::traits::IndexedEnum).
+pub struct Indexed {}
+
+#[sealed]
+impl CopySelector for Indexed {
+    const IS_ZERO_COPY: bool = false;
+}
+
 /**
 
 Marker trait for data specifying whether it is zero-copy or deep-copy.
@@ -78,8 +88,8 @@ If this happen vectors of `T` or boxed slices of `T` can be ε-copy deserialized
 using a reference to a slice of `T`.
 
 You can make zero-copy your own types, but you must ensure that they do not
-contain references and that they have a fixed memory layout; for structures, this requires
-`repr(C)`. ε-serde will track these conditions at compile time and check them at
+contain references and that they have a fixed memory layout; for structures and unions,
+this requires `repr(C)` (or `repr(transparent)` for newtypes). ε-serde will track these conditions at compile time and check them at
 runtime: in case of failure, serialization will panic.
 
 Since we cannot use negative trait bounds, every type that is used as a parameter of
@@ -0,0 +1,88 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Inria
+ * SPDX-FileCopyrightText: 2025 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+/*!
+
+Traits for enums whose slices are serialized using an indexed layout.
+
+*/
+
+use crate::deser::{self, DeserializeInner, ReadWithPos, SliceWithPos};
+use crate::ser::{self, SerializeInner, WriteWithNames};
+use crate::traits::*;
+
+/**
+
+Trait for enums whose vectors and boxed slices are serialized using an indexed
+layout.
+
+An enum deriving [`Epserde`](epserde_derive::Epserde) with the attribute
+`#[epserde(indexed)]` implements this trait, and its [`CopyType`] is
+[`Indexed`]. All variants must be either unit variants, or tuple variants with
+a single zero-copy field (the _payload_ of the variant).
+
+A slice of such an enum is serialized as a zero-copy slice of tags, a
+zero-copy slice of positions, and, for each variant with a payload, a zero-copy
+slice containing the payloads of all elements of that variant. The position of
+an element is the index of its payload in the slice of payloads of its variant.
+
+As a result, vectors and boxed slices of the enum are ε-copy deserialized as an
+[`IndexedSlice`](crate::deser::IndexedSlice), which provides constant-time
+random access by index. Tags and positions are checked when the slice is
+deserialized, so invalid tags and positions are reported as errors rather than
+detected on access.
+
+You should not implement this trait manually, but rather use the provided
+[derive macro](epserde_derive::Epserde).
+
+*/
+pub trait IndexedEnum: CopyType<Copy = Indexed> + Sized + 'static {
+    /// The type of the tag of the enum.
+    type Tag: ZeroCopy + SerializeInner + DeserializeInner;
+    /// A tuple containing, for each variant with a payload, a slice of payloads.
+    type Payloads<'a>: Copy;
+    /// A tuple containing, for each variant with a payload, a vector of payloads.
+    type FullPayloads;
+    /// The number of variants of the enum.
+    const NUM_VARIANTS: usize;
+
+    /// Return the index of the variant of this value.
+    fn _variant(&self) -> usize;
+
+    /// Return the tag of the variant of this value.
+    fn _tag(&self) -> Self::Tag;
+
+    /// Serialize, for each variant with a payload, the slice of payloads
+    /// of the elements of `items` of that variant.
+    fn _serialize_payloads(items: &[Self], backend: &mut impl WriteWithNames) -> ser::Result<()>;
+
+    /// Fully deserialize the slices of payloads.
+    fn _deserialize_full_payloads(
+        backend: &mut impl ReadWithPos,
+    ) -> deser::Result<Self::FullPayloads>;
+
+    /// ε-copy deserialize the slices of payloads.
+    fn _deserialize_eps_payloads<'a>(
+        backend: &mut SliceWithPos<'a>,
+    ) -> deser::Result<Self::Payloads<'a>>;
+
+    /// Return slices of the given vectors of payloads.
+    fn _as_payloads(payloads: &Self::FullPayloads) -> Self::Payloads<'_>;
+
+    /// Return whether `pos` is a valid position for the variant with the
+    /// given tag, or an [invalid-tag error](deser::Error::InvalidTag) if the
+    /// tag is not valid.
+    fn _check(payloads: &Self::Payloads<'_>, tag: Self::Tag, pos: usize) -> deser::Result<bool>;
+
+    /// Return the value of the variant with the given tag whose payload
+    /// is at position `pos`.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if `tag` and `pos` do not pass [`IndexedEnum::_check`].
+    fn _get(payloads: &Self::Payloads<'_>, tag: Self::Tag, pos: usize) -> Self;
+}
@@ -19,3 +19,6 @@ pub use type_info::*;
 
 pub mod copy_type;
 pub use copy_type::*;
+
+pub mod indexed;
+pub use indexed::*;
@@ -5,9 +5,14 @@
  */
 
 use core::slice;
+#[cfg(feature = "std")]
 use std::io::{Read, Seek, SeekFrom, Write};
 
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
 use maligned::{Alignment, A16};
+#[cfg(feature = "mem_dbg")]
 use mem_dbg::{MemDbg, MemSize};
 
 /// An aligned version of [`Cursor`](std::io::Cursor).
@@ -20,7 +25,8 @@ use mem_dbg::{MemDbg, MemSize};
 /// Note that length and position are stored as `usize` values, so the maximum
 /// length and position are `usize::MAX`. This is different from
 /// [`Cursor`](std::io::Cursor), which uses a `u64`.
-#[derive(Debug, Clone, MemDbg, MemSize)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "mem_dbg", derive(MemDbg, MemSize))]
 pub struct AlignedCursor<T: Alignment = A16> {
     vec: Vec<T>,
     pos: usize,
@@ -40,7 +46,7 @@ impl<T: Alignment> AlignedCursor<T> {
     /// Return a new empty [`AlignedCursor`] with a specified capacity.
     pub fn with_capacity(capacity: usize) -> Self {
         Self {
-            vec: Vec::with_capacity(capacity.div_ceil(std::mem::size_of::<T>())),
+            vec: Vec::with_capacity(capacity.div_ceil(core::mem::size_of::<T>())),
             pos: 0,
             len: 0,
         }
@@ -95,43 +101,31 @@ impl<T: Alignment> AlignedCursor<T> {
     pub fn set_position(&mut self, pos: usize) {
         self.pos = pos;
     }
-}
-
-impl<T: Alignment> Default for AlignedCursor<T> {
-    fn default() -> Self {
-        Self::new()
-    }
-}
 
-impl<T: Alignment> Read for AlignedCursor<T> {
-    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+    /// Copy data at the current position into `buf`, returning the number
+    /// of bytes copied.
+    fn read_at_pos(&mut self, buf: &mut [u8]) -> usize {
         if self.pos >= self.len {
-            return Ok(0);
+            return 0;
         }
         let pos = self.pos;
         let rem = self.len - pos;
-        let to_copy = std::cmp::min(buf.len(), rem) as usize;
+        let to_copy = core::cmp::min(buf.len(), rem);
         buf[..to_copy].copy_from_slice(&self.as_bytes()[pos..pos + to_copy]);
         self.pos += to_copy;
-        Ok(to_copy)
+        to_copy
     }
-}
-
-impl<T: Alignment> Write for AlignedCursor<T> {
-    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
-        let len = buf.len().min(usize::MAX - self.pos);
-        if !buf.is_empty() && len == 0 {
-            return Err(std::io::Error::new(
-                std::io::ErrorKind::InvalidInput,
-                "write operation overflows usize::MAX length limit",
-            ));
-        }
 
-        let cap = self.vec.len().saturating_mul(std::mem::size_of::<T>());
+    /// Write `buf` at the current position, extending the storage as needed.
+    ///
+    /// The caller must guarantee that the final position is at most `usize::MAX`.
+    fn write_at_pos(&mut self, buf: &[u8]) {
+        let len = buf.len();
+        let cap = self.vec.len().saturating_mul(core::mem::size_of::<T>());
         let rem = cap - self.pos;
         if rem < len {
             self.vec.resize(
-                (self.pos + len).div_ceil(std::mem::size_of::<T>()),
+                (self.pos + len).div_ceil(core::mem::size_of::<T>()),
                 T::default(),
             );
         }
@@ -142,12 +136,40 @@ impl<T: Alignment> Write for AlignedCursor<T> {
         let bytes = unsafe {
             slice::from_raw_parts_mut(
                 self.vec.as_mut_ptr() as *mut u8,
-                self.vec.len() * std::mem::size_of::<T>(),
+                self.vec.len() * core::mem::size_of::<T>(),
             )
         };
         bytes[pos..pos + len].copy_from_slice(buf);
         self.pos += len;
         self.len = self.len.max(self.pos);
+    }
+}
+
+impl<T: Alignment> Default for AlignedCursor<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: Alignment> Read for AlignedCursor<T> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        Ok(self.read_at_pos(buf))
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: Alignment> Write for AlignedCursor<T> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let len = buf.len().min(usize::MAX - self.pos);
+        if !buf.is_empty() && len == 0 {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "write operation overflows usize::MAX length limit",
+            ));
+        }
+
+        self.write_at_pos(&buf[..len]);
         Ok(len)
     }
 
@@ -156,6 +178,7 @@ impl<T: Alignment> Write for AlignedCursor<T> {
     }
 }
 
+#[cfg(feature = "std")]
 impl<T: Alignment> Seek for AlignedCursor<T> {
     fn seek(&mut self, style: SeekFrom) -> std::io::Result<u64> {
         let (base_pos, offset) = match style {
@@ -190,6 +213,32 @@ impl<T: Alignment> Seek for AlignedCursor<T> {
     }
 }
 
+#[cfg(not(feature = "std"))]
+impl<T: Alignment> crate::deser::ReadNoStd for AlignedCursor<T> {
+    fn read_exact(&mut self, buf: &mut [u8]) -> crate::deser::Result<()> {
+        if self.len.saturating_sub(self.pos) < buf.len() {
+            return Err(crate::deser::Error::read_error(None));
+        }
+        self.read_at_pos(buf);
+        Ok(())
+    }
+}
+
+#[cfg(not(feature = "std"))]
+impl<T: Alignment> crate::ser::WriteNoStd for AlignedCursor<T> {
+    fn write_all(&mut self, buf: &[u8]) -> crate::ser::Result<()> {
+        if buf.len() > usize::MAX - self.pos {
+            return Err(crate::ser::Error::write_error(None));
+        }
+        self.write_at_pos(buf);
+        Ok(())
+    }
+
+    fn flush(&mut self) -> crate::ser::Result<()> {
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::error::Error;
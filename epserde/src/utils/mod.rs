@@ -4,5 +4,9 @@
  * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
  */
 
+#[cfg(feature = "alloc")]
 mod aligned_cursor;
+#[cfg(feature = "alloc")]
 pub use aligned_cursor::AlignedCursor;
+#[cfg(feature = "async")]
+pub(crate) mod sync_bridge;
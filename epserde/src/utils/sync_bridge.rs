@@ -0,0 +1,159 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Inria
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+//! Bridges between the synchronous (de)serialization code, running in a
+//! [blocking task](tokio::task::spawn_blocking), and asynchronous backends.
+//!
+//! Data is passed in chunks of [`CHUNK_SIZE`] bytes through a channel of
+//! capacity [`CHANNEL_CAPACITY`], so the additional memory needed is bounded
+//! independently of the size of the data.
+
+use core::future::{poll_fn, Future};
+use core::pin::Pin;
+use core::task::Poll;
+use std::io::{self, Read, Write};
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+use tokio::sync::mpsc::{Receiver, Sender};
+use tokio::task::JoinHandle;
+
+/// The size of the chunks passed through the channel.
+pub(crate) const CHUNK_SIZE: usize = 1 << 16;
+
+/// The capacity of the channel, in chunks.
+pub(crate) const CHANNEL_CAPACITY: usize = 4;
+
+/// A [`Write`] implementation sending chunks of the data written to a
+/// channel.
+///
+/// Writes fail with [`io::ErrorKind::BrokenPipe`] if the receiver has been
+/// dropped. Data is sent when a chunk is full, or when calling
+/// [`flush`](Write::flush), which must be called at the end.
+pub(crate) struct ChannelWriter {
+    sender: Sender<Vec<u8>>,
+    buffer: Vec<u8>,
+}
+
+impl ChannelWriter {
+    pub(crate) fn new(sender: Sender<Vec<u8>>) -> Self {
+        Self {
+            sender,
+            buffer: Vec::with_capacity(CHUNK_SIZE),
+        }
+    }
+
+    fn send(&mut self) -> io::Result<()> {
+        let chunk = core::mem::replace(&mut self.buffer, Vec::with_capacity(CHUNK_SIZE));
+        self.sender
+            .blocking_send(chunk)
+            .map_err(|_| io::Error::from(io::ErrorKind::BrokenPipe))
+    }
+}
+
+impl Write for ChannelWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let len = buf.len().min(CHUNK_SIZE - self.buffer.len());
+        self.buffer.extend_from_slice(&buf[..len]);
+        if self.buffer.len() == CHUNK_SIZE {
+            self.send()?;
+        }
+        Ok(len)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        if !self.buffer.is_empty() {
+            self.send()?;
+        }
+        Ok(())
+    }
+}
+
+/// A [`Read`] implementation receiving chunks of data from a channel.
+///
+/// The end of the data is reached when the sender is dropped.
+pub(crate) struct ChannelReader {
+    receiver: Receiver<Vec<u8>>,
+    chunk: Vec<u8>,
+    pos: usize,
+}
+
+impl ChannelReader {
+    pub(crate) fn new(receiver: Receiver<Vec<u8>>) -> Self {
+        Self {
+            receiver,
+            chunk: Vec::new(),
+            pos: 0,
+        }
+    }
+}
+
+impl Read for ChannelReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        while self.pos == self.chunk.len() {
+            match self.receiver.blocking_recv() {
+                Some(chunk) => {
+                    self.chunk = chunk;
+                    self.pos = 0;
+                }
+                None => return Ok(0),
+            }
+        }
+        let len = buf.len().min(self.chunk.len() - self.pos);
+        buf[..len].copy_from_slice(&self.chunk[self.pos..self.pos + len]);
+        self.pos += len;
+        Ok(len)
+    }
+}
+
+/// Wait for a blocking task, resuming its panic, if any.
+///
+/// Cancellation (which happens only when the runtime is shutting down) is
+/// reported as an I/O error.
+pub(crate) async fn join<T>(task: JoinHandle<T>) -> io::Result<T> {
+    match task.await {
+        Ok(result) => Ok(result),
+        Err(err) if err.is_panic() => std::panic::resume_unwind(err.into_panic()),
+        Err(err) => Err(io::Error::other(err)),
+    }
+}
+
+/// Read some bytes from an asynchronous backend into `buf`, returning the
+/// number of bytes read, or `None` if `stop` completes first.
+pub(crate) async fn read_until(
+    backend: &mut (impl AsyncRead + Unpin),
+    buf: &mut [u8],
+    mut stop: Pin<&mut impl Future>,
+) -> Option<io::Result<usize>> {
+    let mut buf = ReadBuf::new(buf);
+    poll_fn(|cx| {
+        if stop.as_mut().poll(cx).is_ready() {
+            return Poll::Ready(None);
+        }
+        Pin::new(&mut *backend)
+            .poll_read(cx, &mut buf)
+            .map(|res| Some(res.map(|()| buf.filled().len())))
+    })
+    .await
+}
+
+/// Write all of `buf` to an asynchronous backend.
+pub(crate) async fn write_all(
+    backend: &mut (impl AsyncWrite + Unpin),
+    mut buf: &[u8],
+) -> io::Result<()> {
+    while !buf.is_empty() {
+        let len = poll_fn(|cx| Pin::new(&mut *backend).poll_write(cx, buf)).await?;
+        if len == 0 {
+            return Err(io::ErrorKind::WriteZero.into());
+        }
+        buf = &buf[len..];
+    }
+    Ok(())
+}
+
+/// Flush an asynchronous backend.
+pub(crate) async fn flush(backend: &mut (impl AsyncWrite + Unpin)) -> io::Result<()> {
+    poll_fn(|cx| Pin::new(&mut *backend).poll_flush(cx)).await
+}
@@ -0,0 +1,13 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Inria
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+//! Helpers shared by integration tests.
+
+/// Return a path in the temporary directory for a test file, made unique
+/// across concurrently running test binaries by the process id.
+pub fn temp_path(name: &str) -> std::path::PathBuf {
+    std::env::temp_dir().join(format!("epserde_{}_{}", std::process::id(), name))
+}
@@ -0,0 +1,132 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Inria
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+#![cfg(test)]
+
+use epserde::archive::*;
+use epserde::prelude::*;
+
+mod common;
+use common::temp_path;
+
+#[derive(Epserde, Debug, PartialEq, Eq, Clone)]
+struct Data<A> {
+    a: A,
+    b: Vec<i32>,
+}
+
+fn write_archive(path: impl AsRef<std::path::Path>) {
+    let mut writer = ArchiveWriter::create(path)
+        .unwrap()
+        .with_slice_alignment(SliceAlignment::PAGE);
+    writer
+        .add("numbers", &(0..100_000_u64).collect::<Vec<_>>())
+        .unwrap();
+    writer
+        .add(
+            "data",
+            &Data {
+                a: vec![1_u16, 2, 3],
+                b: vec![-1, -2],
+            },
+        )
+        .unwrap();
+    writer.add("empty", &Vec::<u8>::new()).unwrap();
+    writer.finish().unwrap();
+}
+
+fn check_archive(archive: Archive) {
+    assert_eq!(archive.len(), 3);
+    assert_eq!(
+        archive.names().collect::<Vec<_>>(),
+        vec!["numbers", "data", "empty"]
+    );
+    assert!(archive.contains("data"));
+    assert!(!archive.contains("missing"));
+
+    let numbers = archive.get::<Vec<u64>>("numbers").unwrap();
+    let data = archive.get::<Data<Vec<u16>>>("data").unwrap();
+    let empty = archive.get::<Vec<u8>>("empty").unwrap();
+    // The entries share the memory, which outlives the archive
+    drop(archive);
+
+    let (numbers, data, empty) = (numbers.get(), data.get(), empty.get());
+    assert_eq!(numbers.len(), 100_000);
+    assert_eq!(numbers[99_999], 99_999);
+    assert_eq!(numbers.as_ptr() as usize % 4096, 0);
+    assert_eq!(data.a, [1, 2, 3]);
+    assert_eq!(data.b, vec![-1, -2]);
+    assert!(empty.is_empty());
+}
+
+#[test]
+fn test_archive() {
+    write_archive(temp_path("test_archive.bin"));
+
+    check_archive(Archive::load_mem(temp_path("test_archive.bin")).unwrap());
+    #[cfg(feature = "mmap")]
+    check_archive(Archive::mmap(temp_path("test_archive.bin"), Flags::empty()).unwrap());
+
+    let archive = Archive::load_mem(temp_path("test_archive.bin")).unwrap();
+    assert!(matches!(
+        archive.get::<Vec<u64>>("missing"),
+        Err(Error::MissingEntry(_))
+    ));
+    assert!(matches!(
+        archive.get::<Vec<u32>>("numbers"),
+        Err(Error::Load(LoadError::Deser(
+            deser::Error::WrongTypeHash { .. }
+        )))
+    ));
+
+    std::fs::remove_file(temp_path("test_archive.bin")).unwrap();
+}
+
+#[test]
+fn test_corrupted_archive() {
+    let mut writer = ArchiveWriter::new(Vec::new()).unwrap();
+    writer.add("a", &vec![1_u32, 2, 3]).unwrap();
+    let bytes = writer.finish().unwrap();
+
+    // Truncated
+    assert!(matches!(
+        Archive::from_owned_bytes(bytes[..bytes.len() - 1].to_vec()),
+        Err(Error::FooterError)
+    ));
+    // Wrong magic cookie
+    let mut wrong = bytes.clone();
+    wrong[0] ^= 1;
+    assert!(matches!(
+        Archive::from_owned_bytes(wrong),
+        Err(Error::MagicCookieError(_))
+    ));
+    // Wrong offsets of the table of contents
+    let mut wrong = bytes.clone();
+    let footer = wrong.len() - 24;
+    wrong[footer..footer + 8].copy_from_slice(&u64::MAX.to_ne_bytes());
+    assert!(matches!(
+        Archive::from_owned_bytes(wrong),
+        Err(Error::InvalidTableOfContents)
+    ));
+}
+
+#[test]
+fn test_duplicate_name() {
+    let mut writer = ArchiveWriter::new(Vec::new()).unwrap();
+    writer.add("a", &1_u32).unwrap();
+    assert!(matches!(
+        writer.add("a", &2_u32),
+        Err(ser::Error::DuplicateEntry(name)) if name == "a"
+    ));
+    // The archive is still usable
+    writer.add("b", &2_u32).unwrap();
+    let bytes = writer.finish().unwrap();
+    std::fs::write(temp_path("test_duplicate.bin"), bytes).unwrap();
+    let archive = Archive::load_mem(temp_path("test_duplicate.bin")).unwrap();
+    assert_eq!(archive.len(), 2);
+    assert_eq!(*archive.get::<u32>("b").unwrap().get(), 2);
+    std::fs::remove_file(temp_path("test_duplicate.bin")).unwrap();
+}
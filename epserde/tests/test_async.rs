@@ -0,0 +1,137 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Inria
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+#![cfg(all(test, feature = "async"))]
+
+use epserde::prelude::*;
+use std::sync::Arc;
+
+mod common;
+use common::temp_path;
+
+#[derive(Epserde, Debug, PartialEq, Eq, Default, Clone)]
+struct Data<A> {
+    a: A,
+    name: String,
+    opt: Option<Vec<u16>>,
+}
+
+#[tokio::test]
+async fn test_async() {
+    let data = Data {
+        a: (0..1000_u64).collect::<Vec<_>>(),
+        name: "data".to_string(),
+        opt: Some(vec![1, 2, 3]),
+    };
+
+    // The bytes are the same as those of synchronous serialization
+    let mut expected = Vec::new();
+    let expected_len = data.serialize(&mut expected).unwrap();
+    let mut bytes = Vec::new();
+    let len = Arc::new(data.clone())
+        .serialize_async(&mut bytes)
+        .await
+        .unwrap();
+    assert_eq!(len, expected_len);
+    assert_eq!(bytes, expected);
+
+    let full = <Data<Vec<u64>>>::deserialize_full_async(&mut bytes.as_slice())
+        .await
+        .unwrap();
+    assert_eq!(full, data);
+
+    let err = <Data<Vec<u32>>>::deserialize_full_async(&mut bytes.as_slice()).await;
+    assert!(matches!(err, Err(deser::Error::WrongTypeHash { .. })));
+
+    std::fs::write(temp_path("test_async.bin"), &bytes).unwrap();
+    let case = <Data<Vec<u64>>>::load_mem_async(temp_path("test_async.bin"))
+        .await
+        .unwrap();
+    let eps = case.get();
+    assert_eq!(eps.a, data.a.as_slice());
+    assert_eq!(eps.name, data.name);
+    assert_eq!(eps.opt, data.opt);
+    std::fs::remove_file(temp_path("test_async.bin")).unwrap();
+
+    assert!(matches!(
+        <Data<Vec<u64>>>::load_mem_async(temp_path("test_async.bin")).await,
+        Err(LoadError::Io(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_async_large() {
+    // Much larger than the bounded buffer used by the bridge
+    let data = Data {
+        a: (0..1_000_000_u64).collect::<Vec<_>>(),
+        name: "large".to_string(),
+        opt: None,
+    };
+    let mut expected = Vec::new();
+    data.serialize(&mut expected).unwrap();
+
+    let data = Arc::new(data);
+    let mut bytes = Vec::new();
+    let len = data.clone().serialize_async(&mut bytes).await.unwrap();
+    assert_eq!(len, expected.len());
+    assert_eq!(bytes, expected);
+
+    let full = <Data<Vec<u64>>>::deserialize_full_async(&mut bytes.as_slice())
+        .await
+        .unwrap();
+    assert_eq!(&full, data.as_ref());
+
+    // Data following the structure is ignored
+    let mut extended = bytes.clone();
+    extended.extend_from_slice(&[0xff; 1000]);
+    let full = <Data<Vec<u64>>>::deserialize_full_async(&mut extended.as_slice())
+        .await
+        .unwrap();
+    assert_eq!(&full, data.as_ref());
+
+    // Truncated data
+    let err = <Data<Vec<u64>>>::deserialize_full_async(&mut &bytes[..bytes.len() / 2])
+        .await
+        .unwrap_err();
+    assert!(matches!(err.root(), deser::Error::ReadError { .. }));
+}
+
+/// A stream that never ends, as a connection that is kept open: once its
+/// data has been read, reads are pending forever.
+struct OpenStream<'a>(&'a [u8]);
+
+impl tokio::io::AsyncRead for OpenStream<'_> {
+    fn poll_read(
+        mut self: std::pin::Pin<&mut Self>,
+        _cx: &mut std::task::Context<'_>,
+        buf: &mut tokio::io::ReadBuf<'_>,
+    ) -> std::task::Poll<std::io::Result<()>> {
+        if self.0.is_empty() {
+            return std::task::Poll::Pending;
+        }
+        let len = self.0.len().min(buf.remaining());
+        buf.put_slice(&self.0[..len]);
+        self.0 = &self.0[len..];
+        std::task::Poll::Ready(Ok(()))
+    }
+}
+
+#[tokio::test]
+async fn test_async_open_stream() {
+    let data = Data {
+        a: (0..100_000_u64).collect::<Vec<_>>(),
+        name: "open".to_string(),
+        opt: Some(vec![4, 5]),
+    };
+    let mut bytes = Vec::new();
+    data.serialize(&mut bytes).unwrap();
+
+    // Deserialization completes without waiting for the end of the stream
+    let full = <Data<Vec<u64>>>::deserialize_full_async(&mut OpenStream(&bytes))
+        .await
+        .unwrap();
+    assert_eq!(full, data);
+}
@@ -130,3 +130,95 @@ fn test_wrong_endianess() {
         panic!("wrong error type: {:?}", err);
     }
 }
+
+#[test]
+fn test_read_error() {
+    let data = vec![1_u64; 100];
+    let mut cursor = <AlignedCursor<A16>>::new();
+    data.serialize(&mut cursor).unwrap();
+    let len = cursor.len() - 8;
+    let bytes = &cursor.as_bytes()[..len];
+
+    let err = <Vec<u64>>::deserialize_full(&mut std::io::Cursor::new(bytes)).unwrap_err();
+    assert!(matches!(&err, deser::Error::InField { path, .. } if path == "ROOT"));
+    let deser::Error::ReadError { pos, source } = err.root() else {
+        panic!("wrong error type: {:?}", err);
+    };
+    assert_eq!(*pos, Some(bytes.len() - 99 * 8));
+    assert_eq!(
+        source.as_ref().unwrap().kind(),
+        std::io::ErrorKind::UnexpectedEof
+    );
+    assert!(err.to_string().contains("at position"));
+
+    // Truncated in the middle of the header
+    let err = <Vec<u64>>::deserialize_eps(&bytes[..10]).unwrap_err();
+    assert!(matches!(
+        err,
+        deser::Error::ReadError {
+            pos: Some(10),
+            source: None
+        }
+    ));
+}
+
+#[derive(Epserde, Debug, PartialEq)]
+struct Inner {
+    a: Option<u32>,
+    c: Vec<u16>,
+}
+
+#[derive(Epserde, Debug, PartialEq)]
+struct Outer {
+    x: u64,
+    b: Inner,
+}
+
+/// Return a serialized [`Outer`] and the offset of the tag of its
+/// [`Option`] field.
+fn outer_with_tag_offset() -> (AlignedCursor<A16>, usize) {
+    let data = Outer {
+        x: 0,
+        b: Inner {
+            a: Some(1),
+            c: vec![1, 2, 3],
+        },
+    };
+    let mut cursor = <AlignedCursor<A16>>::new();
+    let schema = data.serialize_with_schema(&mut cursor).unwrap();
+    let tag_offset = schema
+        .0
+        .iter()
+        .find(|row| row.field == "ROOT.b.a.Tag")
+        .unwrap()
+        .offset;
+    (cursor, tag_offset)
+}
+
+#[test]
+fn test_error_path() {
+    let (mut cursor, tag_offset) = outer_with_tag_offset();
+
+    // A bad option tag
+    cursor.as_bytes_mut()[tag_offset] = 2;
+    let err = <Outer>::deserialize_eps(cursor.as_bytes()).unwrap_err();
+    let deser::Error::InField { path, pos, error } = &err else {
+        panic!("wrong error type: {:?}", err);
+    };
+    assert_eq!(path, "ROOT.b.a");
+    assert_eq!(*pos, tag_offset + 1);
+    assert!(matches!(**error, deser::Error::InvalidTag(2)));
+    assert!(err.to_string().contains("in field ROOT.b.a"));
+
+    let err = <Outer>::deserialize_full(&mut std::io::Cursor::new(cursor.as_bytes())).unwrap_err();
+    assert!(matches!(&err, deser::Error::InField { path, .. } if path == "ROOT.b.a"));
+    assert!(matches!(err.root(), deser::Error::InvalidTag(2)));
+
+    // A truncated slice
+    cursor.as_bytes_mut()[tag_offset] = 1;
+    let len = cursor.len() - 2;
+    let err = <Outer>::deserialize_full(&mut std::io::Cursor::new(&cursor.as_bytes()[..len]))
+        .unwrap_err();
+    assert!(matches!(&err, deser::Error::InField { path, .. } if path == "ROOT.b.c"));
+    assert!(matches!(err.root(), deser::Error::ReadError { .. }));
+}
@@ -9,6 +9,7 @@
 /*
 
 // This test should not compile, as the field of a zero-copy structure is not zero-copy.
+// See the compile_fail examples in the documentation of SerializeInner.
 
 #[test]
 fn test_fake_zero() {
@@ -43,3 +44,46 @@ fn test_fake_zero() {
     assert!(result.is_err());
 }
 */
+
+use epserde::prelude::*;
+
+#[derive(Epserde, Debug)]
+struct Data {
+    name: String,
+    items: Vec<u64>,
+}
+
+#[test]
+fn test_write_error() {
+    let data = Data {
+        name: "data".to_string(),
+        items: vec![0; 1000],
+    };
+    // A mutable slice returns WriteZero when full
+    let mut buf = [0_u8; 200];
+    let err = data.serialize(&mut &mut buf[..]).unwrap_err();
+    let ser::Error::WriteError {
+        pos, source, path, ..
+    } = &err
+    else {
+        panic!("wrong error type: {:?}", err);
+    };
+    assert!(pos.unwrap() <= 200);
+    assert_eq!(
+        source.as_ref().unwrap().kind(),
+        std::io::ErrorKind::WriteZero
+    );
+    assert_eq!(path, "ROOT.items");
+    assert!(core::error::Error::source(&err).is_some());
+    assert!(err.to_string().contains("in field ROOT.items"));
+
+    // Same path with a schema writer
+    let err = data.serialize_with_schema(&mut &mut buf[..]).unwrap_err();
+    assert!(matches!(err, ser::Error::WriteError { path, .. } if path == "ROOT.items"));
+
+    // Failure in the header
+    let err = data.serialize(&mut &mut buf[..10]).unwrap_err();
+    assert!(
+        matches!(err, ser::Error::WriteError { pos: Some(10), path, .. } if path == "VERSION_MINOR")
+    );
+}
@@ -0,0 +1,76 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Inria
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+#![cfg(test)]
+
+use core::cell::RefCell;
+use epserde::prelude::*;
+use epserde::ser::{Diagnostic, DiagnosticSink, SchemaWriter, WriterWithPos};
+use maligned::A16;
+
+struct Collector {
+    threshold: usize,
+    diagnostics: RefCell<Vec<Diagnostic>>,
+}
+
+impl DiagnosticSink for Collector {
+    fn report(&self, diagnostic: &Diagnostic) {
+        self.diagnostics.borrow_mut().push(diagnostic.clone());
+    }
+
+    fn large_deep_copy_slice_threshold(&self) -> usize {
+        self.threshold
+    }
+}
+
+#[test]
+fn test_large_deep_copy_slice() {
+    let data = vec![vec![0_u8; 2]; 10];
+    let sink = Collector {
+        threshold: 5,
+        diagnostics: RefCell::new(vec![]),
+    };
+
+    let mut cursor = <AlignedCursor<A16>>::new();
+    let mut writer = WriterWithPos::new(&mut cursor).with_diagnostics(&sink);
+    data.serialize_on_field_write(&mut writer).unwrap();
+    assert_eq!(
+        *sink.diagnostics.borrow(),
+        vec![Diagnostic::LargeDeepCopySlice {
+            type_name: core::any::type_name::<Vec<u8>>(),
+            len: 10,
+        }]
+    );
+
+    // Schema writers forward diagnostics to the underlying writer
+    sink.diagnostics.borrow_mut().clear();
+    let mut cursor = <AlignedCursor<A16>>::new();
+    let mut writer = WriterWithPos::new(&mut cursor).with_diagnostics(&sink);
+    let mut schema_writer = SchemaWriter::new(&mut writer);
+    data.serialize_on_field_write(&mut schema_writer).unwrap();
+    assert_eq!(sink.diagnostics.borrow().len(), 1);
+
+    // Short slices are not reported
+    sink.diagnostics.borrow_mut().clear();
+    let mut cursor = <AlignedCursor<A16>>::new();
+    let mut writer = WriterWithPos::new(&mut cursor).with_diagnostics(&sink);
+    vec![vec![0_u8; 2]; 5]
+        .serialize_on_field_write(&mut writer)
+        .unwrap();
+    assert!(sink.diagnostics.borrow().is_empty());
+}
+
+#[test]
+fn test_closure_sink() {
+    let count = RefCell::new(0);
+    let sink = |_: &Diagnostic| *count.borrow_mut() += 1;
+    let data = vec![String::new(); 1 << 21];
+
+    let mut cursor = <AlignedCursor<A16>>::new();
+    let mut writer = WriterWithPos::new(&mut cursor).with_diagnostics(&sink);
+    data.serialize_on_field_write(&mut writer).unwrap();
+    assert_eq!(*count.borrow(), 1);
+}
@@ -0,0 +1,142 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Inria
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+#![cfg(test)]
+
+use epserde::prelude::*;
+use maligned::A16;
+
+#[derive(Epserde, Debug, PartialEq, Eq, Clone, Copy)]
+#[repr(C)]
+#[zero_copy]
+struct Leaf {
+    value: u32,
+}
+
+#[derive(Epserde, Debug, PartialEq, Eq, Clone, Copy)]
+#[repr(C)]
+#[zero_copy]
+struct Inner {
+    left: usize,
+    right: usize,
+    key: u64,
+}
+
+#[derive(Epserde, Debug, PartialEq, Eq, Clone, Copy)]
+#[repr(u8)]
+#[epserde(indexed)]
+enum Node {
+    Empty = 3,
+    Leaf(Leaf) = 1,
+    Inner(Inner) = 2,
+}
+
+fn nodes() -> Vec<Node> {
+    (0..100)
+        .map(|i| match i % 3 {
+            0 => Node::Empty,
+            1 => Node::Leaf(Leaf { value: i }),
+            _ => Node::Inner(Inner {
+                left: i as usize,
+                right: i as usize + 1,
+                key: i as u64 * 1000,
+            }),
+        })
+        .collect()
+}
+
+#[test]
+fn test_indexed_vec() {
+    let nodes = nodes();
+    let mut cursor = <AlignedCursor<A16>>::new();
+    nodes.serialize(&mut cursor).unwrap();
+
+    cursor.set_position(0);
+    let full = <Vec<Node>>::deserialize_full(&mut cursor).unwrap();
+    assert_eq!(full, nodes);
+
+    let eps = <Vec<Node>>::deserialize_eps(cursor.as_bytes()).unwrap();
+    assert_eq!(eps.len(), nodes.len());
+    for (i, node) in nodes.iter().enumerate() {
+        assert_eq!(eps.get(i), Some(*node));
+    }
+    assert_eq!(eps.get(nodes.len()), None);
+    assert_eq!(eps.iter().collect::<Vec<_>>(), nodes);
+}
+
+#[test]
+fn test_indexed_boxed_slice() {
+    let nodes = nodes().into_boxed_slice();
+    let mut cursor = <AlignedCursor<A16>>::new();
+    nodes.serialize(&mut cursor).unwrap();
+
+    cursor.set_position(0);
+    let full = <Box<[Node]>>::deserialize_full(&mut cursor).unwrap();
+    assert_eq!(full, nodes);
+
+    let eps = <Box<[Node]>>::deserialize_eps(cursor.as_bytes()).unwrap();
+    assert_eq!(eps.iter().collect::<Vec<_>>(), nodes.to_vec());
+}
+
+#[test]
+fn test_indexed_single() {
+    let node = Node::Inner(Inner {
+        left: 1,
+        right: 2,
+        key: 3,
+    });
+    let mut cursor = <AlignedCursor<A16>>::new();
+    node.serialize(&mut cursor).unwrap();
+
+    cursor.set_position(0);
+    let full = <Node>::deserialize_full(&mut cursor).unwrap();
+    assert_eq!(full, node);
+    let eps = <Node>::deserialize_eps(cursor.as_bytes()).unwrap();
+    assert_eq!(eps, node);
+}
+
+#[test]
+fn test_indexed_invalid_tag() {
+    let nodes = vec![Node::Empty, Node::Leaf(Leaf { value: 0 })];
+    let mut cursor = <AlignedCursor<A16>>::new();
+    let schema = nodes.serialize_with_schema(&mut cursor).unwrap();
+    // The first zero-copy slice contains the tags
+    let row = schema
+        .0
+        .iter()
+        .find(|row| row.field == "ROOT.zero")
+        .unwrap();
+    cursor.as_bytes_mut()[row.offset] = 0;
+
+    let err = <Vec<Node>>::deserialize_eps(cursor.as_bytes()).unwrap_err();
+    assert!(matches!(err.root(), deser::Error::InvalidTag(0)));
+    let err =
+        <Vec<Node>>::deserialize_full(&mut std::io::Cursor::new(cursor.as_bytes())).unwrap_err();
+    assert!(matches!(err.root(), deser::Error::InvalidTag(0)));
+}
+
+#[test]
+fn test_indexed_invalid_position() {
+    let nodes = vec![Node::Empty, Node::Leaf(Leaf { value: 0 })];
+    let mut cursor = <AlignedCursor<A16>>::new();
+    let schema = nodes.serialize_with_schema(&mut cursor).unwrap();
+    // The positions are the zero-copy items of type usize
+    let row = schema
+        .0
+        .iter()
+        .filter(|row| row.field == "ROOT.zero" && row.ty == "usize")
+        .nth(1)
+        .unwrap();
+    cursor.as_bytes_mut()[row.offset..row.offset + row.size]
+        .copy_from_slice(&1_usize.to_ne_bytes());
+
+    let err = <Vec<Node>>::deserialize_eps(cursor.as_bytes()).unwrap_err();
+    assert!(matches!(err.root(), deser::Error::InvalidIndexedSlice(1)));
+
+    let err =
+        <Vec<Node>>::deserialize_full(&mut std::io::Cursor::new(cursor.as_bytes())).unwrap_err();
+    assert!(matches!(err.root(), deser::Error::InvalidIndexedSlice(1)));
+}
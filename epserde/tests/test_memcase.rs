@@ -6,8 +6,12 @@
 
 #![cfg(test)]
 
+use epserde::deser::{RefFamily, StableBytes};
 use epserde::prelude::*;
 
+mod common;
+use common::temp_path;
+
 #[derive(Epserde, Debug, PartialEq, Eq, Default, Clone)]
 struct PersonVec<A, B> {
     a: A,
@@ -36,50 +40,384 @@ fn test_mem_case() {
         test: -0xbadf00d,
     };
     // Serialize
-    person.store("test.bin").unwrap();
+    person.store(temp_path("test.bin")).unwrap();
+
+    let case = Person::load_mem(temp_path("test.bin")).unwrap();
 
-    let res = Person::load_mem("test.bin").unwrap();
+    let res = case.get();
     assert_eq!(person.test, res.test);
     assert_eq!(person.a, res.a);
     assert_eq!(person.b.a, res.b.a);
     assert_eq!(person.b.b, res.b.b);
 
-    let res = Person::load_mmap("test.bin", Flags::empty()).unwrap();
+    let case = Person::load_mmap(temp_path("test.bin"), Flags::empty()).unwrap();
+
+    let res = case.get();
     assert_eq!(person.test, res.test);
     assert_eq!(person.a, res.a);
     assert_eq!(person.b.a, res.b.a);
     assert_eq!(person.b.b, res.b.b);
 
-    let res = Person::load_mem("test.bin").unwrap();
+    let case = Person::load_mem(temp_path("test.bin")).unwrap();
+
+    let res = case.get();
     assert_eq!(person.test, res.test);
     assert_eq!(person.a, res.a);
     assert_eq!(person.b.a, res.b.a);
     assert_eq!(person.b.b, res.b.b);
 
-    let res = Person::load_full("test.bin").unwrap();
+    let res = Person::load_full(temp_path("test.bin")).unwrap();
     assert_eq!(person.test, res.test);
     assert_eq!(person.a, res.a);
     assert_eq!(person.b.a, res.b.a);
     assert_eq!(person.b.b, res.b.b);
 
-    let res = Person::mmap("test.bin", Flags::empty()).unwrap();
+    let case = Person::mmap(temp_path("test.bin"), Flags::empty()).unwrap();
+
+    let res = case.get();
     assert_eq!(person.test, res.test);
     assert_eq!(person.a, res.a);
     assert_eq!(person.b.a, res.b.a);
     assert_eq!(person.b.b, res.b.b);
 
-    let res = Person::mmap("test.bin", Flags::TRANSPARENT_HUGE_PAGES).unwrap();
+    let case = Person::mmap(temp_path("test.bin"), Flags::TRANSPARENT_HUGE_PAGES).unwrap();
+
+    let res = case.get();
     assert_eq!(person.test, res.test);
     assert_eq!(person.a, res.a);
     assert_eq!(person.b.a, res.b.a);
     assert_eq!(person.b.b, res.b.b);
 
-    let res = Person::mmap("test.bin", Flags::empty()).unwrap();
+    let case = Person::mmap(temp_path("test.bin"), Flags::empty()).unwrap();
+
+    let res = case.get();
     assert_eq!(person.test, res.test);
     assert_eq!(person.a, res.a);
     assert_eq!(person.b.a, res.b.a);
     assert_eq!(person.b.b, res.b.b);
 
     // cleanup the file
-    std::fs::remove_file("test.bin").unwrap();
+    std::fs::remove_file(temp_path("test.bin")).unwrap();
+}
+
+#[derive(Epserde, Debug, PartialEq, Eq, Default, Clone, Copy)]
+#[repr(C, align(128))]
+#[zero_copy]
+struct Wide {
+    a: u64,
+    b: u64,
+}
+
+#[test]
+fn test_load_mem_aligned() {
+    let wide = Wide { a: 1, b: 2 };
+    wide.store(temp_path("test_aligned.bin")).unwrap();
+
+    assert!(matches!(
+        Wide::load_mem(temp_path("test_aligned.bin")),
+        Err(LoadError::Deser(deser::Error::AlignmentError))
+    ));
+    let case = Wide::load_mem_aligned(temp_path("test_aligned.bin"), 128).unwrap();
+    let res = case.get();
+    assert_eq!(**res, wide);
+    assert!(matches!(
+        Wide::load_mem_aligned(temp_path("test_aligned.bin"), 384),
+        Err(LoadError::Layout(_))
+    ));
+
+    // Large slices are page aligned in memory
+    let data: Vec<u64> = (0..10_000).collect();
+    {
+        let mut file =
+            std::io::BufWriter::new(std::fs::File::create(temp_path("test_aligned.bin")).unwrap());
+        let mut writer =
+            epserde::ser::WriterWithPos::new(&mut file).with_slice_alignment(SliceAlignment::PAGE);
+        data.serialize_on_field_write(&mut writer).unwrap();
+    }
+    let case = <Vec<u64>>::load_mem_aligned(temp_path("test_aligned.bin"), 4096).unwrap();
+    let res = case.get();
+    assert_eq!(*res, data.as_slice());
+    assert_eq!(res.as_ptr() as usize % 4096, 0);
+
+    std::fs::remove_file(temp_path("test_aligned.bin")).unwrap();
+}
+
+/// A user-defined owner of bytes aligned to 8 bytes.
+struct Words(Box<[u64]>);
+
+impl core::ops::Deref for Words {
+    type Target = [u8];
+    fn deref(&self) -> &[u8] {
+        unsafe { core::slice::from_raw_parts(self.0.as_ptr() as *const u8, self.0.len() * 8) }
+    }
+}
+
+unsafe impl StableBytes for Words {}
+
+#[test]
+fn test_from_owned_bytes() {
+    let text = "Hello, world!".to_string();
+    let mut cursor = <AlignedCursor<maligned::A16>>::new();
+    text.serialize(&mut cursor).unwrap();
+    let bytes = cursor.as_bytes().to_vec();
+
+    let case = MemCase::<String>::from_owned_bytes(bytes.clone()).unwrap();
+
+    let res = case.get();
+    assert_eq!(*res, text.as_str());
+    let case = MemCase::<String>::from_owned_bytes(std::sync::Arc::<[u8]>::from(bytes)).unwrap();
+    let res = case.get();
+    assert_eq!(*res, text.as_str());
+
+    let person = Person {
+        a: vec![0x89; 6],
+        b: Data {
+            a: vec![0x42; 7],
+            b: vec![0xbadf00d; 2],
+        },
+        test: -0xbadf00d,
+    };
+    let mut cursor = <AlignedCursor<maligned::A16>>::new();
+    person.serialize(&mut cursor).unwrap();
+    let bytes = cursor.as_bytes();
+    let mut words = vec![0_u64; bytes.len().div_ceil(8)];
+    for (word, chunk) in words.iter_mut().zip(bytes.chunks(8)) {
+        let mut buf = [0; 8];
+        buf[..chunk.len()].copy_from_slice(chunk);
+        *word = u64::from_ne_bytes(buf);
+    }
+    let case = MemCase::<Person>::from_owned_bytes(Words(words.into_boxed_slice())).unwrap();
+    let res = case.get();
+    assert_eq!(person.test, res.test);
+    assert_eq!(person.a, res.a);
+    assert_eq!(person.b.a, res.b.a);
+    assert_eq!(person.b.b, res.b.b);
+}
+
+/// A reader returning at most 7 bytes at a time.
+struct Trickle<'a>(&'a [u8]);
+
+impl std::io::Read for Trickle<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = buf.len().min(self.0.len()).min(7);
+        buf[..n].copy_from_slice(&self.0[..n]);
+        self.0 = &self.0[n..];
+        Ok(n)
+    }
+}
+
+#[test]
+fn test_read_mem() {
+    let person = Person {
+        a: (0..1000).collect(),
+        b: Data {
+            a: vec![0x42; 7],
+            b: vec![0xbadf00d; 2],
+        },
+        test: -0xbadf00d,
+    };
+    let mut cursor = <AlignedCursor<maligned::A16>>::new();
+    person.serialize(&mut cursor).unwrap();
+    let bytes = cursor.as_bytes();
+
+    for len_hint in [0, 100, bytes.len(), 2 * bytes.len()] {
+        let case = Person::read_mem(Trickle(bytes), len_hint).unwrap();
+        let res = case.get();
+        assert_eq!(person.test, res.test);
+        assert_eq!(person.a, res.a);
+        assert_eq!(person.b.a, res.b.a);
+        assert_eq!(person.b.b, res.b.b);
+    }
+
+    assert!(Person::read_mem(std::io::empty(), 0).is_err());
+}
+
+#[cfg(feature = "mmap")]
+#[test]
+fn test_range() {
+    let person = Person {
+        a: vec![0x89; 6],
+        b: Data {
+            a: vec![0x42; 7],
+            b: vec![0xbadf00d; 2],
+        },
+        test: -0xbadf00d,
+    };
+    let data: Vec<u64> = (0..1000).collect();
+
+    // Pack two structures in a file, aligning the second one to 64 bytes
+    let mut file = vec![0xff; 100];
+    let person_offset = file.len();
+    person.serialize(&mut file).unwrap();
+    let person_len = file.len() - person_offset;
+    file.resize(file.len() + epserde::pad_align_to(file.len(), 64), 0xff);
+    let data_offset = file.len();
+    data.serialize(&mut file).unwrap();
+    let data_len = file.len() - data_offset;
+    file.extend_from_slice(&[0xff; 100]);
+    std::fs::write(temp_path("test_range.bin"), &file).unwrap();
+
+    let case =
+        <Vec<u64>>::load_mem_range(temp_path("test_range.bin"), data_offset as u64, data_len)
+            .unwrap();
+
+    let res = case.get();
+    assert_eq!(*res, data.as_slice());
+    let case = <Vec<u64>>::mmap_range(
+        temp_path("test_range.bin"),
+        data_offset as u64,
+        data_len,
+        Flags::empty(),
+    )
+    .unwrap();
+    let res = case.get();
+    assert_eq!(*res, data.as_slice());
+
+    // Loading in memory does not need an aligned offset
+    let case = Person::load_mem_range(
+        temp_path("test_range.bin"),
+        person_offset as u64,
+        person_len,
+    )
+    .unwrap();
+    let res = case.get();
+    assert_eq!(person.a, res.a);
+    assert_eq!(person.b.b, res.b.b);
+
+    assert!(matches!(
+        <Vec<u64>>::load_mem_range(temp_path("test_range.bin"), data_offset as u64, file.len()),
+        Err(LoadError::Io(_))
+    ));
+    assert!(<Vec<u64>>::mmap_range(
+        temp_path("test_range.bin"),
+        u64::MAX,
+        data_len,
+        Flags::empty()
+    )
+    .is_err());
+    assert!(matches!(
+        <Vec<u64>>::load_mem_range(temp_path("test_range.bin"), data_offset as u64, 0),
+        Err(LoadError::EmptyRange(o)) if o == data_offset as u64
+    ));
+    assert!(matches!(
+        <Vec<u64>>::mmap_range(
+            temp_path("test_range.bin"),
+            data_offset as u64,
+            0,
+            Flags::empty()
+        ),
+        Err(LoadError::EmptyRange(o)) if o == data_offset as u64
+    ));
+
+    std::fs::remove_file(temp_path("test_range.bin")).unwrap();
+}
+
+#[test]
+fn test_shared_mem_case() {
+    let person = Person {
+        a: vec![0x89; 6],
+        b: Data {
+            a: vec![0x42; 7],
+            b: vec![0xbadf00d; 2],
+        },
+        test: -0xbadf00d,
+    };
+    person.store(temp_path("test_shared.bin")).unwrap();
+
+    let shared = Person::load_mem(temp_path("test_shared.bin"))
+        .unwrap()
+        .into_shared();
+    let a = shared.map::<RefFamily<[usize]>>(|p, _| p.a);
+    assert_eq!(shared.strong_count(), 2);
+    let handles = (0..4)
+        .map(|_| {
+            let shared = shared.clone();
+            let a = a.clone();
+            std::thread::spawn(move || {
+                assert_eq!(shared.get().test, -0xbadf00d);
+                assert_eq!(**a.get(), [0x89; 6]);
+            })
+        })
+        .collect::<Vec<_>>();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    // The mapped case keeps the backend alive
+    drop(shared);
+    assert_eq!(a.strong_count(), 1);
+    assert_eq!(**a.get(), [0x89; 6]);
+
+    let b = Person::load_mem(temp_path("test_shared.bin"))
+        .unwrap()
+        .map::<Data<Vec<u16>>>(|p, _| p.b);
+    assert_eq!(b.get().a, [0x42; 7]);
+    assert_eq!(b.get().b, vec![0xbadf00d; 2]);
+
+    // project is an alias of map
+    let c = b.project::<RefFamily<[u16]>>(|d, _| d.a);
+    assert_eq!(*c.get(), [0x42; 7]);
+
+    std::fs::remove_file(temp_path("test_shared.bin")).unwrap();
+}
+
+#[test]
+fn test_load_error() {
+    vec![1_u64, 2, 3]
+        .store(temp_path("test_load_error.bin"))
+        .unwrap();
+
+    assert!(matches!(
+        <Vec<u32>>::load_full(temp_path("test_load_error.bin")),
+        Err(LoadError::Deser(deser::Error::WrongTypeHash { .. }))
+    ));
+    assert!(matches!(
+        <Vec<u32>>::load_mem(temp_path("test_load_error.bin")),
+        Err(LoadError::Deser(deser::Error::WrongTypeHash { .. }))
+    ));
+    #[cfg(feature = "mmap")]
+    assert!(matches!(
+        <Vec<u32>>::mmap(temp_path("test_load_error.bin"), Flags::empty()),
+        Err(LoadError::Deser(deser::Error::WrongTypeHash { .. }))
+    ));
+
+    std::fs::remove_file(temp_path("test_load_error.bin")).unwrap();
+    assert!(matches!(
+        <Vec<u64>>::load_mem(temp_path("test_load_error.bin")),
+        Err(LoadError::Io(_))
+    ));
+}
+
+#[cfg(feature = "mmap")]
+#[test]
+fn test_store_mmap() {
+    let person = Person {
+        a: (0..1000).collect(),
+        b: Data {
+            a: vec![0x42; 7],
+            b: vec![0xbadf00d; 2],
+        },
+        test: -0xbadf00d,
+    };
+    person.store(temp_path("test_store_mmap_seq.bin")).unwrap();
+    let mmap = person.store_mmap(temp_path("test_store_mmap.bin")).unwrap();
+    let expected = std::fs::read(temp_path("test_store_mmap_seq.bin")).unwrap();
+    assert_eq!(&mmap[..], expected.as_slice());
+    assert_eq!(
+        std::fs::read(temp_path("test_store_mmap.bin")).unwrap(),
+        expected
+    );
+
+    let case = <MemCase<Person>>::from_mmap(mmap).unwrap();
+    let res = case.get();
+    assert_eq!(person.test, res.test);
+    assert_eq!(person.a, res.a);
+    assert_eq!(person.b.a, res.b.a);
+    assert_eq!(person.b.b, res.b.b);
+    drop(case);
+
+    let case = Person::mmap(temp_path("test_store_mmap.bin"), Flags::empty()).unwrap();
+    assert_eq!(person.a, case.get().a);
+
+    std::fs::remove_file(temp_path("test_store_mmap_seq.bin")).unwrap();
+    std::fs::remove_file(temp_path("test_store_mmap.bin")).unwrap();
 }
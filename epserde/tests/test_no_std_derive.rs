@@ -0,0 +1,71 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Inria
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+//! Checks that the code generated by the derive macros does not refer to
+//! `std`, so that it can be used in `no_std` crates.
+
+#![cfg(test)]
+#![no_std]
+// The types are only used to check that the generated code compiles
+#![allow(dead_code)]
+
+use epserde::prelude::*;
+
+#[derive(Epserde, Clone, Copy)]
+#[repr(C)]
+#[zero_copy]
+struct Zero {
+    a: u32,
+    b: [u16; 3],
+}
+
+#[derive(Epserde, Clone, Copy)]
+#[repr(C)]
+#[zero_copy]
+union Union {
+    a: u32,
+    b: f64,
+}
+
+#[derive(Epserde, Clone, Copy)]
+#[repr(C)]
+#[zero_copy]
+enum ZeroEnum {
+    A,
+    B(u64),
+}
+
+#[derive(Epserde)]
+#[deep_copy]
+struct Deep<T> {
+    a: T,
+    b: u64,
+    #[epserde(since = 1, default)]
+    c: u8,
+}
+
+#[derive(Epserde)]
+#[deep_copy]
+enum Enum<T> {
+    A,
+    B(T),
+    C { x: u32 },
+}
+
+#[test]
+fn test_no_std_derive() {
+    assert!(<Zero as MaxSizeOf>::max_size_of() >= 4);
+    assert!(<Union as MaxSizeOf>::max_size_of() >= 8);
+    assert!(<ZeroEnum as MaxSizeOf>::max_size_of() >= 8);
+    let zero = Zero { a: 0, b: [1, 2, 3] };
+    let deep = Deep {
+        a: zero,
+        b: 4,
+        c: 5,
+    };
+    assert!(deep.serialized_size().unwrap() > 0);
+    assert!(Enum::B(zero).serialized_size().unwrap() > 0);
+}
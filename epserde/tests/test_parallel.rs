@@ -0,0 +1,85 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Inria
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+#![cfg(test)]
+
+use epserde::prelude::*;
+use epserde::ser::{ParallelFileWriter, WriteNoStd, WriterWithPos};
+
+mod common;
+use common::temp_path;
+
+#[derive(Epserde, Debug, PartialEq, Eq, Default, Clone)]
+struct Data<A, B> {
+    a: A,
+    name: String,
+    b: B,
+    small: Vec<u8>,
+}
+
+#[test]
+fn test_store_parallel() {
+    let data = Data {
+        a: (0..300_000_u64).collect::<Vec<_>>(),
+        name: "data".to_string(),
+        b: (0..1_000_000_u32)
+            .map(|x| x.wrapping_mul(31))
+            .collect::<Vec<_>>(),
+        small: vec![1, 2, 3],
+    };
+
+    data.store(temp_path("test_parallel_seq.bin")).unwrap();
+    data.store_parallel(temp_path("test_parallel.bin")).unwrap();
+    let expected = std::fs::read(temp_path("test_parallel_seq.bin")).unwrap();
+    assert_eq!(
+        std::fs::read(temp_path("test_parallel.bin")).unwrap(),
+        expected
+    );
+
+    let full = <Data<Vec<u64>, Vec<u32>>>::load_full(temp_path("test_parallel.bin")).unwrap();
+    assert_eq!(full, data);
+
+    // Small threshold, odd number of threads, and slice alignment
+    let mut expected = Vec::new();
+    let mut writer = WriterWithPos::new(&mut expected).with_slice_alignment(SliceAlignment::PAGE);
+    data.serialize_on_field_write(&mut writer).unwrap();
+
+    let file = std::fs::File::create(temp_path("test_parallel.bin")).unwrap();
+    let mut writer = ParallelFileWriter::new(&file)
+        .with_threads(3)
+        .with_threshold(5)
+        .with_slice_alignment(SliceAlignment::PAGE);
+    data.serialize_on_field_write(&mut writer).unwrap();
+    writer.flush().unwrap();
+    assert_eq!(
+        std::fs::read(temp_path("test_parallel.bin")).unwrap(),
+        expected
+    );
+
+    // Zero threshold and empty slices
+    let data = Data {
+        a: Vec::<u64>::new(),
+        name: String::new(),
+        b: Vec::<u32>::new(),
+        small: vec![],
+    };
+    let mut expected = Vec::new();
+    data.serialize(&mut expected).unwrap();
+
+    let file = std::fs::File::create(temp_path("test_parallel.bin")).unwrap();
+    let mut writer = ParallelFileWriter::new(&file)
+        .with_threads(2)
+        .with_threshold(0);
+    data.serialize_on_field_write(&mut writer).unwrap();
+    writer.flush().unwrap();
+    assert_eq!(
+        std::fs::read(temp_path("test_parallel.bin")).unwrap(),
+        expected
+    );
+
+    std::fs::remove_file(temp_path("test_parallel_seq.bin")).unwrap();
+    std::fs::remove_file(temp_path("test_parallel.bin")).unwrap();
+}
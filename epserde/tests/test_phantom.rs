@@ -28,6 +28,7 @@ fn test_phantom() {
 }
 
 #[derive(Epserde, Debug, PartialEq, Eq, Clone, Default)]
+#[deep_copy]
 struct DataFull<A> {
     a: usize,
     b: PhantomData<A>,
@@ -0,0 +1,64 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Inria
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+#![cfg(test)]
+
+use epserde::prelude::*;
+use epserde::ser::{SizeCounter, WriteWithPos, WriterWithPos};
+
+#[derive(Epserde, Debug, PartialEq, Eq, Clone)]
+struct Data<A, B> {
+    a: A,
+    name: String,
+    b: B,
+    opt: Option<u16>,
+}
+
+fn check<T: Serialize>(value: &T) {
+    let mut cursor = <AlignedCursor<maligned::A16>>::new();
+    let len = value.serialize(&mut cursor).unwrap();
+    assert_eq!(value.serialized_size().unwrap(), len);
+    assert_eq!(cursor.len(), len);
+}
+
+#[test]
+fn test_serialized_size() {
+    check(&0_u8);
+    check(&1_u64);
+    check(&String::from("hello"));
+    check(&vec![1_u8, 2, 3]);
+    check(&vec![1_u64, 2, 3]);
+    check(&vec![vec![1_u32, 2], vec![], vec![3]]);
+    check(&Some(vec![1_u16; 7]));
+    check(&[1_u32, 2, 3]);
+    check(&Data {
+        a: (0..100_u64).collect::<Vec<_>>(),
+        name: "data".to_string(),
+        b: vec![1_u8, 2, 3],
+        opt: Some(5),
+    });
+}
+
+#[test]
+fn test_size_counter_slice_alignment() {
+    let data = Data {
+        a: (0..1000_u64).collect::<Vec<_>>(),
+        name: "data".to_string(),
+        b: vec![1_u8; 10],
+        opt: None,
+    };
+    let slice_alignment = SliceAlignment::new(256, 4096);
+
+    let mut cursor = <AlignedCursor<maligned::A16>>::new();
+    let mut writer = WriterWithPos::new(&mut cursor).with_slice_alignment(slice_alignment);
+    data.serialize_on_field_write(&mut writer).unwrap();
+    let len = writer.pos();
+
+    let mut counter = SizeCounter::new().with_slice_alignment(slice_alignment);
+    data.serialize_on_field_write(&mut counter).unwrap();
+    assert_eq!(counter.pos(), len);
+    assert!(counter.pos() > data.serialized_size().unwrap());
+}
@@ -11,6 +11,7 @@ use epserde::prelude::*;
 use maligned::A16;
 
 #[derive(Epserde, Debug, PartialEq, Eq, Clone)]
+#[allow(dead_code)]
 struct Data<A: PartialEq = usize, const Q: usize = 3> {
     a: A,
     b: [i32; Q],
@@ -0,0 +1,116 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Inria
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+#![cfg(test)]
+
+use epserde::prelude::*;
+use epserde::ser::{SchemaWriter, WriterWithPos};
+use maligned::A16;
+
+// Fields must be generic to be ε-copy deserialized as slices
+#[derive(Epserde, Debug, PartialEq, Eq, Clone)]
+struct DataGeneric<S, L, T> {
+    small: S,
+    large: L,
+    text: T,
+}
+
+type Data = DataGeneric<Vec<u32>, Vec<u64>, String>;
+
+fn data() -> Data {
+    DataGeneric {
+        small: vec![1, 2, 3],
+        large: (0..300).collect(),
+        text: "a".repeat(200),
+    }
+}
+
+fn offset(bytes: &[u8], ptr: *const u8) -> usize {
+    ptr as usize - bytes.as_ptr() as usize
+}
+
+#[test]
+fn test_slice_alignment() {
+    let data = data();
+    let slice_alignment = SliceAlignment::new(256, 4096);
+
+    let mut cursor = <AlignedCursor<A16>>::new();
+    let mut writer = WriterWithPos::new(&mut cursor).with_slice_alignment(slice_alignment);
+    data.serialize_on_field_write(&mut writer).unwrap();
+
+    cursor.set_position(0);
+    let full = Data::deserialize_full(&mut cursor).unwrap();
+    assert_eq!(full, data);
+
+    let bytes = cursor.as_bytes();
+    let eps = Data::deserialize_eps(bytes).unwrap();
+    assert_eq!(eps.small, data.small.as_slice());
+    assert_eq!(eps.large, data.large.as_slice());
+    assert_eq!(eps.text, data.text.as_str());
+    assert_eq!(offset(bytes, eps.large.as_ptr() as *const u8) % 4096, 0);
+    assert_ne!(offset(bytes, eps.small.as_ptr() as *const u8) % 4096, 0);
+    assert_ne!(offset(bytes, eps.text.as_ptr()) % 4096, 0);
+
+    // The schema writer must produce the same bytes
+    let mut schema_cursor = <AlignedCursor<A16>>::new();
+    let mut writer = WriterWithPos::new(&mut schema_cursor).with_slice_alignment(slice_alignment);
+    let mut schema_writer = SchemaWriter::new(&mut writer);
+    data.serialize_on_field_write(&mut schema_writer).unwrap();
+    let large = schema_writer
+        .schema
+        .0
+        .iter()
+        .find(|row| row.field == "ROOT.large.zero")
+        .unwrap();
+    assert_eq!(large.offset % 4096, 0);
+    assert_eq!(schema_cursor.as_bytes(), cursor.as_bytes());
+}
+
+#[test]
+fn test_no_slice_alignment() {
+    let data = data();
+    let mut cursor = <AlignedCursor<A16>>::new();
+    data.serialize(&mut cursor).unwrap();
+    // Without slice alignment, the data is smaller than a page
+    assert!(cursor.len() < 4096);
+    let eps = Data::deserialize_eps(cursor.as_bytes()).unwrap();
+    assert_eq!(eps.large, data.large.as_slice());
+}
+
+#[test]
+fn test_minor_version_1() {
+    // Files with minor version 1 have no slice alignment in the header
+    let data = data();
+    let mut cursor = <AlignedCursor<A16>>::new();
+    data.serialize(&mut cursor).unwrap();
+    let bytes = cursor.as_bytes();
+
+    // Magic cookie, major version, minor version, and usize size take 13
+    // bytes, followed by threshold and alignment; removing 16 bytes does
+    // not change the alignment of the following data
+    let mut old = bytes[..13].to_vec();
+    old[10..12].copy_from_slice(&1_u16.to_ne_bytes());
+    old.extend_from_slice(&bytes[29..]);
+
+    let mut old_cursor = <AlignedCursor<A16>>::new();
+    std::io::Write::write_all(&mut old_cursor, &old).unwrap();
+    old_cursor.set_position(0);
+    let full = Data::deserialize_full(&mut old_cursor).unwrap();
+    assert_eq!(full, data);
+    let eps = Data::deserialize_eps(old_cursor.as_bytes()).unwrap();
+    assert_eq!(eps.large, data.large.as_slice());
+}
+
+#[test]
+fn test_invalid_slice_alignment() {
+    let mut cursor = <AlignedCursor<A16>>::new();
+    data().serialize(&mut cursor).unwrap();
+    cursor.as_bytes_mut()[21..29].copy_from_slice(&3_usize.to_ne_bytes());
+    assert!(matches!(
+        Data::deserialize_eps(cursor.as_bytes()),
+        Err(deser::Error::InvalidSliceAlignment(3))
+    ));
+}
@@ -0,0 +1,49 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Inria
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+#![cfg(test)]
+
+use epserde::prelude::*;
+
+#[test]
+fn test_store_atomic() {
+    let dir = std::env::temp_dir().join(format!("epserde_store_atomic_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("data.bin");
+
+    let old = vec![1_u64; 1000];
+    old.store_atomic(&path, true).unwrap();
+    assert_eq!(<Vec<u64>>::load_full(&path).unwrap(), old);
+
+    // A mapping of the previous file is not affected by a new store
+    #[cfg(feature = "mmap")]
+    let case = <Vec<u64>>::mmap(&path, Flags::empty()).unwrap();
+
+    let new = (0..2000_u64).collect::<Vec<_>>();
+    new.store_atomic(&path, false).unwrap();
+    assert_eq!(<Vec<u64>>::load_full(&path).unwrap(), new);
+    #[cfg(feature = "mmap")]
+    assert_eq!(*case.get(), old.as_slice());
+
+    let mut expected = Vec::new();
+    new.serialize(&mut expected).unwrap();
+    assert_eq!(std::fs::read(&path).unwrap(), expected);
+
+    // No temporary files are left behind
+    let names = std::fs::read_dir(&dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name())
+        .collect::<Vec<_>>();
+    assert_eq!(names, ["data.bin"]);
+
+    // Errors are reported, and no temporary file is created
+    assert!(matches!(
+        new.store_atomic(dir.join("missing").join("data.bin"), false),
+        Err(epserde::ser::Error::FileOpenError(_))
+    ));
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
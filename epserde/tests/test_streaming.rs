@@ -0,0 +1,178 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Inria
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+#![cfg(test)]
+
+use epserde::prelude::*;
+use epserde::ser::{
+    ParallelFileWriter, SchemaWriter, StreamingVec, StreamingVecWriter, WriteNoStd, WriterWithPos,
+};
+use maligned::A16;
+
+mod common;
+use common::temp_path;
+
+#[derive(Epserde, Debug, PartialEq, Eq, Clone)]
+struct Index<O> {
+    name: String,
+    offsets: O,
+    count: u8,
+}
+
+fn squares(n: u64) -> impl Iterator<Item = u64> {
+    (0..n).map(|x| x * x)
+}
+
+#[test]
+fn test_streaming_vec_writer() {
+    let mut expected = <AlignedCursor<A16>>::new();
+    squares(1000)
+        .collect::<Vec<_>>()
+        .serialize(&mut expected)
+        .unwrap();
+
+    let mut cursor = <AlignedCursor<A16>>::new();
+    let mut writer = WriterWithPos::new(&mut cursor).with_patching();
+    let mut vec_writer = <StreamingVecWriter<u64, _>>::with_header(&mut writer).unwrap();
+    assert!(vec_writer.is_empty());
+    vec_writer.extend(squares(500)).unwrap();
+    for x in squares(1000).skip(500) {
+        vec_writer.push(&x).unwrap();
+    }
+    assert_eq!(vec_writer.len(), 1000);
+    assert_eq!(vec_writer.finish().unwrap(), 1000);
+    assert_eq!(cursor.as_bytes(), expected.as_bytes());
+
+    let eps = <Vec<u64>>::deserialize_eps(cursor.as_bytes()).unwrap();
+    assert!(eps.iter().copied().eq(squares(1000)));
+}
+
+#[test]
+fn test_streaming_vec_field() {
+    let data = Index {
+        name: "squares".to_string(),
+        offsets: squares(1000).collect::<Vec<_>>(),
+        count: 3,
+    };
+    let streaming = Index {
+        name: "squares".to_string(),
+        offsets: StreamingVec::new(squares(1000)),
+        count: 3,
+    };
+    let mut cursor = <AlignedCursor<A16>>::new();
+    let mut writer = WriterWithPos::new(&mut cursor).with_patching();
+    streaming.serialize_on_field_write(&mut writer).unwrap();
+    cursor.set_position(0);
+    let full = <Index<Vec<u64>>>::deserialize_full(&mut cursor).unwrap();
+    assert_eq!(full, data);
+    let eps = <Index<Vec<u64>>>::deserialize_eps(cursor.as_bytes()).unwrap();
+    assert_eq!(eps.offsets, data.offsets.as_slice());
+
+    // The schema writer supports patching if its writer does
+    let streaming = Index {
+        name: "squares".to_string(),
+        offsets: StreamingVec::new(squares(1000)),
+        count: 3,
+    };
+    let mut cursor = <AlignedCursor<A16>>::new();
+    let mut writer = WriterWithPos::new(&mut cursor).with_patching();
+    let mut schema_writer = SchemaWriter::new(&mut writer);
+    streaming
+        .serialize_on_field_write(&mut schema_writer)
+        .unwrap();
+    assert!(schema_writer
+        .schema
+        .0
+        .iter()
+        .any(|row| row.field == "ROOT.offsets.len"));
+    // A single row for all items
+    let rows = schema_writer
+        .schema
+        .0
+        .iter()
+        .filter(|row| row.field == "ROOT.offsets.zero")
+        .collect::<Vec<_>>();
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0].size, 1000 * core::mem::size_of::<u64>());
+    let eps = <Index<Vec<u64>>>::deserialize_eps(cursor.as_bytes()).unwrap();
+    assert_eq!(eps.offsets, data.offsets.as_slice());
+}
+
+#[test]
+fn test_streaming_vec_store() {
+    let streaming = Index {
+        name: "squares".to_string(),
+        offsets: StreamingVec::new(squares(100_000)),
+        count: 3,
+    };
+    streaming.store(temp_path("test_streaming.bin")).unwrap();
+    let full = <Index<Vec<u64>>>::load_full(temp_path("test_streaming.bin")).unwrap();
+    assert!(full.offsets.into_iter().eq(squares(100_000)));
+
+    let streaming = StreamingVec::new(squares(100_000));
+    streaming
+        .store_atomic(temp_path("test_streaming.bin"), false)
+        .unwrap();
+    let full = <Vec<u64>>::load_full(temp_path("test_streaming.bin")).unwrap();
+    assert!(full.into_iter().eq(squares(100_000)));
+
+    // The length is patched after the buffer has been written to the file
+    let streaming = StreamingVec::new(squares(100_000));
+    let file = std::fs::File::create(temp_path("test_streaming.bin")).unwrap();
+    let mut writer = ParallelFileWriter::new(&file)
+        .with_threads(2)
+        .with_threshold(1024);
+    streaming.serialize_on_field_write(&mut writer).unwrap();
+    writer.flush().unwrap();
+    let full = <Vec<u64>>::load_full(temp_path("test_streaming.bin")).unwrap();
+    assert!(full.into_iter().eq(squares(100_000)));
+
+    std::fs::remove_file(temp_path("test_streaming.bin")).unwrap();
+}
+
+#[test]
+fn test_streaming_vec_errors() {
+    // Patching is not enabled by default
+    let streaming = StreamingVec::new(squares(10));
+    let mut cursor = <AlignedCursor<A16>>::new();
+    assert!(matches!(
+        streaming.serialize(&mut cursor),
+        Err(epserde::ser::Error::PatchError(_))
+    ));
+    // The iterator has been consumed
+    assert!(matches!(
+        streaming.serialized_size(),
+        Err(epserde::ser::Error::AlreadyConsumed)
+    ));
+
+    // Slice alignment is not supported, and it is checked up front
+    let slice_alignment = SliceAlignment::new(256, 4096);
+    let mut cursor = <AlignedCursor<A16>>::new();
+    let mut writer = WriterWithPos::new(&mut cursor)
+        .with_slice_alignment(slice_alignment)
+        .with_patching();
+    assert!(matches!(
+        <StreamingVecWriter<u64, _>>::with_header(&mut writer),
+        Err(epserde::ser::Error::UnsupportedSliceAlignment(256))
+    ));
+    assert!(matches!(
+        <StreamingVecWriter<u64, _>>::new(&mut writer),
+        Err(epserde::ser::Error::UnsupportedSliceAlignment(256))
+    ));
+    assert_eq!(cursor.len(), 0);
+}
+
+#[test]
+#[cfg(debug_assertions)]
+fn test_streaming_vec_writer_unfinished() {
+    let mut cursor = <AlignedCursor<A16>>::new();
+    let mut writer = WriterWithPos::new(&mut cursor).with_patching();
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        let mut vec_writer = <StreamingVecWriter<u64, _>>::new(&mut writer).unwrap();
+        vec_writer.push(&1).unwrap();
+    }));
+    assert!(result.is_err());
+}
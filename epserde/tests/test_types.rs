@@ -142,6 +142,7 @@ fn test_vec_vec_array_array_usize() {
 #[test]
 fn test_struct_deep() {
     #[derive(Epserde, Copy, Clone, Debug, PartialEq)]
+    #[deep_copy]
     struct Struct {
         a: usize,
         b: usize,
@@ -188,6 +189,7 @@ fn test_struct_zero() {
 #[test]
 fn test_tuple_struct_deep() {
     #[derive(Epserde, Copy, Clone, Debug, PartialEq)]
+    #[deep_copy]
     struct Tuple(usize, usize, i32);
     let a = Tuple(0, 1, 2);
     let mut cursor = <AlignedCursor<A16>>::new();
@@ -281,7 +283,7 @@ fn test_enum_deep() {
     assert!(matches!(eps, Data::E));
 
     let mut cursor = <AlignedCursor<A16>>::new();
-    let a = Vec::from_iter(iter::repeat(Data::A).take(10));
+    let a = Vec::from_iter(iter::repeat_n(Data::A, 10));
     a.serialize(&mut cursor).unwrap();
     cursor.set_position(0);
     let full = <Vec<Data>>::deserialize_full(&mut cursor).unwrap();
@@ -341,7 +343,7 @@ fn test_enum_zero() {
     assert_eq!(a, *eps);
 
     let mut cursor = <AlignedCursor<A16>>::new();
-    let a = Vec::from_iter(iter::repeat(Data::A).take(10));
+    let a = Vec::from_iter(iter::repeat_n(Data::A, 10));
     a.serialize(&mut cursor).unwrap();
     cursor.set_position(0);
     let full = <Vec<Data>>::deserialize_full(&mut cursor).unwrap();
@@ -349,3 +351,140 @@ fn test_enum_zero() {
     let eps = <Vec<Data>>::deserialize_eps(cursor.as_bytes()).unwrap();
     assert_eq!(a, *eps);
 }
+
+#[test]
+fn test_enum_tags() {
+    #[derive(Epserde, Clone, Debug, PartialEq)]
+    #[repr(u8)]
+    enum Data {
+        A = 10,
+        B(u64) = 3,
+        C,
+        D { a: i32, b: Vec<i32> } = 20,
+    }
+
+    for (a, tag) in [
+        (Data::A, 10),
+        (Data::B(3), 3),
+        (Data::C, 4),
+        (
+            Data::D {
+                a: 1,
+                b: vec![1, 2],
+            },
+            20,
+        ),
+    ] {
+        let mut cursor = <AlignedCursor<A16>>::new();
+        let schema = a.serialize_with_schema(&mut cursor).unwrap();
+        let row = schema
+            .0
+            .iter()
+            .find(|row| row.field.ends_with("tag"))
+            .unwrap();
+        assert_eq!(row.size, 1);
+        assert_eq!(cursor.as_bytes()[row.offset], tag);
+
+        cursor.set_position(0);
+        let full = <Data>::deserialize_full(&mut cursor).unwrap();
+        assert_eq!(a, full);
+        let eps = <Data>::deserialize_eps(cursor.as_bytes()).unwrap();
+        assert_eq!(a, eps);
+
+        // Invalid tag
+        cursor.as_bytes_mut()[row.offset] = 0;
+        let err = <Data>::deserialize_eps(cursor.as_bytes()).unwrap_err();
+        assert!(matches!(err.root(), deser::Error::InvalidTag(0)));
+    }
+
+    #[derive(Epserde, Clone, Debug, PartialEq)]
+    #[deep_copy]
+    #[epserde(tag = "i16")]
+    enum Signed {
+        A = -2,
+        B,
+        C,
+    }
+
+    let mut cursor = <AlignedCursor<A16>>::new();
+    let schema = Signed::A.serialize_with_schema(&mut cursor).unwrap();
+    let row = schema
+        .0
+        .iter()
+        .find(|row| row.field.ends_with("tag"))
+        .unwrap();
+    assert_eq!(row.size, 2);
+    cursor.set_position(0);
+    assert_eq!(<Signed>::deserialize_full(&mut cursor).unwrap(), Signed::A);
+    let mut cursor = <AlignedCursor<A16>>::new();
+    Signed::C.serialize(&mut cursor).unwrap();
+    assert_eq!(
+        <Signed>::deserialize_eps(cursor.as_bytes()).unwrap(),
+        Signed::C
+    );
+
+    // Negative invalid tags are reported without truncation
+    let mut cursor = <AlignedCursor<A16>>::new();
+    Signed::A.serialize(&mut cursor).unwrap();
+    cursor.as_bytes_mut()[row.offset..row.offset + 2].copy_from_slice(&(-5_i16).to_ne_bytes());
+    let err = <Signed>::deserialize_eps(cursor.as_bytes());
+    assert!(matches!(
+        err.unwrap_err().root(),
+        deser::Error::InvalidTag(-5)
+    ));
+}
+
+#[test]
+fn test_enum_discriminant_type_hash() {
+    fn type_hash<T: TypeHash>() -> u64 {
+        let mut hasher = xxhash_rust::xxh3::Xxh3::new();
+        T::type_hash(&mut hasher);
+        hasher.digest()
+    }
+
+    mod a {
+        use epserde::prelude::*;
+        #[derive(Epserde)]
+        #[deep_copy]
+        pub enum Data {
+            A = 0,
+            B = 1,
+        }
+    }
+
+    mod b {
+        use epserde::prelude::*;
+        #[derive(Epserde)]
+        #[deep_copy]
+        pub enum Data {
+            A = 1,
+            B = 0,
+        }
+    }
+
+    mod c {
+        use epserde::prelude::*;
+        #[derive(Epserde)]
+        #[deep_copy]
+        #[epserde(tag = "u8")]
+        pub enum Data {
+            A = 0,
+            B = 1,
+        }
+    }
+
+    mod d {
+        use epserde::prelude::*;
+        #[derive(Epserde)]
+        #[deep_copy]
+        pub enum Data {
+            B = 1,
+            A = 0,
+        }
+    }
+
+    assert_ne!(type_hash::<a::Data>(), type_hash::<b::Data>());
+    assert_ne!(type_hash::<a::Data>(), type_hash::<c::Data>());
+    // The order of the variants is irrelevant
+    assert_eq!(type_hash::<a::Data>(), type_hash::<d::Data>());
+}
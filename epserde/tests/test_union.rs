@@ -0,0 +1,87 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Inria
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+#![cfg(test)]
+
+use epserde::prelude::*;
+use maligned::A16;
+use xxhash_rust::xxh3::Xxh3;
+
+#[derive(Epserde, Debug, PartialEq, Eq, Clone, Copy)]
+#[repr(transparent)]
+#[zero_copy]
+struct Id(u64);
+
+#[derive(Epserde, Clone, Copy)]
+#[repr(C)]
+#[zero_copy]
+union Value {
+    int: i32,
+    float: f64,
+    pair: (u8, u16),
+}
+
+#[derive(Epserde, Clone, Copy)]
+#[repr(C)]
+#[zero_copy]
+struct Struct {
+    int: i32,
+    float: f64,
+    pair: (u8, u16),
+}
+
+#[test]
+fn test_transparent() {
+    const { assert!(<Id as SerializeInner>::IS_ZERO_COPY) };
+    assert_eq!(Id::max_size_of(), u64::max_size_of());
+
+    let data = vec![Id(1), Id(2), Id(3)];
+    let mut cursor = <AlignedCursor<A16>>::new();
+    data.serialize(&mut cursor).unwrap();
+
+    cursor.set_position(0);
+    let full = <Vec<Id>>::deserialize_full(&mut cursor).unwrap();
+    assert_eq!(full, data);
+    let eps = <Vec<Id>>::deserialize_eps(cursor.as_bytes()).unwrap();
+    assert_eq!(eps, data.as_slice());
+}
+
+#[test]
+fn test_union() {
+    const { assert!(<Value as SerializeInner>::IS_ZERO_COPY) };
+    assert_eq!(Value::max_size_of(), 8);
+
+    let data = [Value { float: 1.5 }, Value { int: -3 }];
+    let mut cursor = <AlignedCursor<A16>>::new();
+    data.serialize(&mut cursor).unwrap();
+
+    cursor.set_position(0);
+    let full = <[Value; 2]>::deserialize_full(&mut cursor).unwrap();
+    assert_eq!(unsafe { full[0].float }, 1.5);
+    assert_eq!(unsafe { full[1].int }, -3);
+    let eps = <[Value; 2]>::deserialize_eps(cursor.as_bytes()).unwrap();
+    assert_eq!(unsafe { eps[0].float }, 1.5);
+    assert_eq!(unsafe { eps[1].int }, -3);
+}
+
+#[test]
+fn test_union_hashes() {
+    // A union and a struct with the same fields must have different hashes
+    let mut union_hasher = Xxh3::new();
+    Value::type_hash(&mut union_hasher);
+    let mut struct_hasher = Xxh3::new();
+    Struct::type_hash(&mut struct_hasher);
+    assert_ne!(union_hasher.digest(), struct_hasher.digest());
+
+    let mut union_hasher = Xxh3::new();
+    let mut offset_of = 0;
+    Value::repr_hash(&mut union_hasher, &mut offset_of);
+    assert_eq!(offset_of, core::mem::size_of::<Value>());
+    let mut struct_hasher = Xxh3::new();
+    let mut offset_of = 0;
+    Struct::repr_hash(&mut struct_hasher, &mut offset_of);
+    assert_ne!(union_hasher.digest(), struct_hasher.digest());
+}
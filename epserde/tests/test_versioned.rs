@@ -0,0 +1,140 @@
+/*
+ * SPDX-FileCopyrightText: 2025 Inria
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+#![cfg(test)]
+
+use epserde::prelude::*;
+use maligned::A16;
+
+mod v0 {
+    use epserde::prelude::*;
+
+    #[derive(Epserde, Debug, PartialEq, Eq, Clone)]
+    pub struct Data {
+        pub a: usize,
+        pub b: Vec<u32>,
+    }
+}
+
+mod v1 {
+    use epserde::prelude::*;
+
+    #[derive(Epserde, Debug, PartialEq, Eq, Clone)]
+    pub struct Data {
+        pub a: usize,
+        pub b: Vec<u32>,
+        #[epserde(since = 1, default)]
+        pub c: Vec<u64>,
+    }
+}
+
+mod v2 {
+    use epserde::prelude::*;
+
+    #[derive(Epserde, Debug, PartialEq, Eq, Clone)]
+    pub struct Data {
+        pub a: usize,
+        pub b: Vec<u32>,
+        #[epserde(since = 1, default)]
+        pub c: Vec<u64>,
+        #[epserde(since = 2, default)]
+        pub d: Option<i32>,
+    }
+}
+
+#[test]
+fn test_same_type_hash() {
+    let mut h1 = xxhash_rust::xxh3::Xxh3::new();
+    let mut h2 = xxhash_rust::xxh3::Xxh3::new();
+    <v1::Data>::type_hash(&mut h1);
+    <v2::Data>::type_hash(&mut h2);
+    assert_eq!(h1.digest(), h2.digest());
+
+    let mut h1 = xxhash_rust::xxh3::Xxh3::new();
+    let mut h2 = xxhash_rust::xxh3::Xxh3::new();
+    <v1::Data>::repr_hash(&mut h1, &mut 0);
+    <v2::Data>::repr_hash(&mut h2, &mut 0);
+    assert_eq!(h1.digest(), h2.digest());
+}
+
+#[test]
+fn test_old_data_new_code() {
+    let data = v1::Data {
+        a: 42,
+        b: vec![1, 2, 3],
+        c: vec![4, 5],
+    };
+
+    let mut cursor = <AlignedCursor<A16>>::new();
+    data.serialize(&mut cursor).unwrap();
+
+    // Full-copy deserialization
+    cursor.set_position(0);
+    let full = <v2::Data>::deserialize_full(&mut cursor).unwrap();
+    assert_eq!(full.a, 42);
+    assert_eq!(full.b, vec![1, 2, 3]);
+    assert_eq!(full.c, vec![4, 5]);
+    assert_eq!(full.d, None);
+
+    // ε-copy deserialization
+    let eps = <v2::Data>::deserialize_eps(cursor.as_bytes()).unwrap();
+    assert_eq!(eps.a, 42);
+    assert_eq!(eps.b, [1, 2, 3]);
+    assert_eq!(eps.c, [4, 5]);
+    assert_eq!(eps.d, None);
+
+    // Same version
+    let full = <v1::Data>::deserialize_full(&mut std::io::Cursor::new(cursor.as_bytes())).unwrap();
+    assert_eq!(full, data);
+}
+
+#[test]
+fn test_new_data_old_code() {
+    let data = v2::Data {
+        a: 42,
+        b: vec![1, 2, 3],
+        c: vec![4, 5],
+        d: Some(-1),
+    };
+
+    let mut cursor = <AlignedCursor<A16>>::new();
+    data.serialize(&mut cursor).unwrap();
+
+    let err = <v1::Data>::deserialize_full(&mut std::io::Cursor::new(cursor.as_bytes()));
+    assert!(matches!(
+        err.unwrap_err().root(),
+        deser::Error::UnsupportedVersion(2)
+    ));
+    let err = <v1::Data>::deserialize_eps(cursor.as_bytes());
+    assert!(matches!(
+        err.unwrap_err().root(),
+        deser::Error::UnsupportedVersion(2)
+    ));
+}
+
+#[test]
+fn test_unversioned_is_incompatible() {
+    let data = v0::Data {
+        a: 42,
+        b: vec![1, 2, 3],
+    };
+
+    let mut cursor = <AlignedCursor<A16>>::new();
+    data.serialize(&mut cursor).unwrap();
+
+    // Adding the first versioned field changes the type hash, as
+    // unversioned data contain no version
+    let err = <v1::Data>::deserialize_full(&mut std::io::Cursor::new(cursor.as_bytes()));
+    assert!(matches!(
+        err.unwrap_err(),
+        deser::Error::WrongTypeHash { .. }
+    ));
+    let err = <v1::Data>::deserialize_eps(cursor.as_bytes());
+    assert!(matches!(
+        err.unwrap_err(),
+        deser::Error::WrongTypeHash { .. }
+    ));
+}